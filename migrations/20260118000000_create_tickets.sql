@@ -0,0 +1,18 @@
+-- IF NOT EXISTS lets databases whose table was created by hand adopt this migration
+CREATE TABLE IF NOT EXISTS tickets
+(
+    id          UUID PRIMARY KEY,
+    title       TEXT   NOT NULL,
+    description TEXT   NOT NULL,
+    status      TEXT   NOT NULL,
+    assignee    UUID,
+    version     BIGINT NOT NULL DEFAULT 0
+);
+
+-- Early versions stored the status as its Rust variant name ("Open")
+UPDATE tickets
+SET status = lower(status)
+WHERE status <> lower(status);
+
+CREATE INDEX IF NOT EXISTS tickets_status_idx ON tickets (lower(status));
+CREATE INDEX IF NOT EXISTS tickets_assignee_idx ON tickets (assignee);
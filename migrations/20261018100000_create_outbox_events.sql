@@ -0,0 +1,14 @@
+CREATE TABLE outbox_events
+(
+    id             BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    -- Consumers deduplicate on event_id, since delivery is at-least-once
+    event_id       UUID        NOT NULL UNIQUE,
+    aggregate_type TEXT        NOT NULL,
+    aggregate_id   UUID        NOT NULL,
+    event_type     TEXT        NOT NULL,
+    payload        JSONB       NOT NULL,
+    occurred_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    published_at   TIMESTAMPTZ
+);
+
+CREATE INDEX outbox_events_pending_idx ON outbox_events (id) WHERE published_at IS NULL;
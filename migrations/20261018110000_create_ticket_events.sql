@@ -0,0 +1,11 @@
+-- Append-only event streams for the event-sourced ticket store; the primary
+-- key rejects a second event at the same position of a stream
+CREATE TABLE ticket_events
+(
+    ticket_id   UUID        NOT NULL,
+    version     BIGINT      NOT NULL,
+    event_type  TEXT        NOT NULL,
+    payload     JSONB       NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (ticket_id, version)
+);
@@ -0,0 +1,13 @@
+CREATE TABLE ticket_audit
+(
+    id          BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    ticket_id   UUID        NOT NULL,
+    -- NULL for the entry recording the creation of the ticket
+    previous    JSONB,
+    current     JSONB       NOT NULL,
+    actor       TEXT,
+    trace_id    TEXT,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX ticket_audit_ticket_id_idx ON ticket_audit (ticket_id, id);
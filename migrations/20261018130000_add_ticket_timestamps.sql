@@ -0,0 +1,7 @@
+-- Existing tickets get the migration time, as their real history is unknown
+ALTER TABLE tickets
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN closed_at  TIMESTAMPTZ;
+
+UPDATE tickets SET closed_at = updated_at WHERE status = 'closed';
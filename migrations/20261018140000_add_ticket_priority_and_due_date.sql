@@ -0,0 +1,7 @@
+ALTER TABLE tickets
+    ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium'
+        CHECK (priority IN ('low', 'medium', 'high', 'critical')),
+    ADD COLUMN due_date DATE;
+
+CREATE INDEX tickets_priority_idx ON tickets (priority);
+CREATE INDEX tickets_due_date_idx ON tickets (due_date) WHERE due_date IS NOT NULL;
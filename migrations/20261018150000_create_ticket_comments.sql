@@ -0,0 +1,11 @@
+-- No foreign key to tickets: with the event store, tickets have no row there
+CREATE TABLE ticket_comments
+(
+    id         UUID        PRIMARY KEY,
+    ticket_id  UUID        NOT NULL,
+    body       TEXT        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX ticket_comments_ticket_id_idx ON ticket_comments (ticket_id, created_at, id);
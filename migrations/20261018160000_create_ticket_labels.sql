@@ -0,0 +1,14 @@
+CREATE TABLE labels
+(
+    id   BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE ticket_labels
+(
+    ticket_id UUID   NOT NULL REFERENCES tickets (id) ON DELETE CASCADE,
+    label_id  BIGINT NOT NULL REFERENCES labels (id),
+    PRIMARY KEY (ticket_id, label_id)
+);
+
+CREATE INDEX ticket_labels_label_id_idx ON ticket_labels (label_id);
@@ -0,0 +1,18 @@
+CREATE TABLE users
+(
+    id         UUID        PRIMARY KEY,
+    name       TEXT        NOT NULL,
+    active     BOOLEAN     NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Assignees used to be unchecked ids; register them so they stay assignable
+INSERT INTO users (id, name)
+SELECT assignee, 'Unknown user'
+FROM tickets
+WHERE assignee IS NOT NULL
+UNION
+SELECT (payload ->> 'user_id')::uuid, 'Unknown user'
+FROM ticket_events
+WHERE event_type IN ('TicketAssigned', 'TicketProgressStarted')
+ON CONFLICT (id) DO NOTHING;
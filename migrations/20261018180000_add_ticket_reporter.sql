@@ -0,0 +1,2 @@
+-- Subject of the authenticated actor who created the ticket
+ALTER TABLE tickets ADD COLUMN reporter TEXT;
@@ -0,0 +1,11 @@
+CREATE TABLE idempotency_keys
+(
+    actor            TEXT        NOT NULL,
+    key              TEXT        NOT NULL,
+    request_hash     TEXT        NOT NULL,
+    response_status  SMALLINT    NOT NULL,
+    response_headers JSONB       NOT NULL,
+    response_body    TEXT,
+    created_at       TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (actor, key)
+);
@@ -0,0 +1,16 @@
+-- Full-text search over titles and descriptions, titles weighted higher
+ALTER TABLE tickets
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED;
+
+CREATE INDEX tickets_search_vector_idx ON tickets USING GIN (search_vector);
+
+-- The text search parser does not split Japanese into words, so substring
+-- matches are served by trigram indexes instead. pg_trgm only makes trigrams
+-- of non-ASCII characters in a UTF-8 database.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX tickets_title_trgm_idx ON tickets USING GIN (title gin_trgm_ops);
+CREATE INDEX tickets_description_trgm_idx ON tickets USING GIN (description gin_trgm_ops);
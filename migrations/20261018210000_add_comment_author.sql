@@ -0,0 +1,2 @@
+-- Subject of the authenticated actor who wrote the comment
+ALTER TABLE ticket_comments ADD COLUMN author TEXT;
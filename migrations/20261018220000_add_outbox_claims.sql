@@ -0,0 +1,4 @@
+-- Set while a relay delivers the event, so no transaction stays open across
+-- the delivery. A relay that dies leaves a claim that lapses, and the event is
+-- delivered again.
+ALTER TABLE outbox_events ADD COLUMN claimed_until TIMESTAMPTZ;
@@ -0,0 +1,28 @@
+-- Current state of each event-sourced ticket, written in the same transaction
+-- as its events, so lists and searches filter here instead of folding every
+-- stream. Streams appended before this table existed are projected when the
+-- event store starts.
+CREATE TABLE ticket_projections
+(
+    ticket_id   UUID        PRIMARY KEY,
+    -- Position of the last event folded into this row
+    version     BIGINT      NOT NULL,
+    title       TEXT        NOT NULL,
+    description TEXT        NOT NULL,
+    status      TEXT        NOT NULL,
+    assignee    UUID,
+    priority    TEXT        NOT NULL,
+    due_date    DATE,
+    labels      TEXT[]      NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL,
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED
+);
+
+CREATE INDEX ticket_projections_labels_idx ON ticket_projections USING GIN (labels);
+CREATE INDEX ticket_projections_search_vector_idx ON ticket_projections USING GIN (search_vector);
+CREATE INDEX ticket_projections_title_trgm_idx ON ticket_projections USING GIN (title gin_trgm_ops);
+CREATE INDEX ticket_projections_description_trgm_idx
+    ON ticket_projections USING GIN (description gin_trgm_ops);
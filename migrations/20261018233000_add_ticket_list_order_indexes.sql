@@ -0,0 +1,3 @@
+-- Lists are ordered by creation time, ties broken by id
+CREATE INDEX tickets_created_at_id_idx ON tickets (created_at, id);
+CREATE INDEX ticket_projections_created_at_id_idx ON ticket_projections (created_at, ticket_id);
@@ -0,0 +1,6 @@
+-- Escapes text for HTML, so search headlines can wrap matches in markup
+-- without letting ticket text inject any
+CREATE FUNCTION html_escape(value TEXT) RETURNS TEXT
+    LANGUAGE sql IMMUTABLE STRICT PARALLEL SAFE
+    RETURN replace(replace(replace(replace(replace(
+        value, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;');
@@ -1 +1,2 @@
+pub mod policy;
 pub mod usecase;
\ No newline at end of file
@@ -0,0 +1,172 @@
+use crate::domain::actor::Actor;
+use crate::domain::comments::comment::Comment;
+use crate::domain::error::{DomainError, Result};
+use crate::domain::tickets::ticket::Ticket;
+
+/// What an actor wants to do to an existing ticket, its comments or users.
+///
+/// Creating and reading tickets and adding comments is open to every
+/// authenticated actor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Edit,
+    Label,
+    Assign,
+    Unassign,
+    Start,
+    Resolve,
+    Reopen,
+    Close,
+    EditComment,
+    DeleteComment,
+    RegisterUser,
+    DeactivateUser,
+}
+
+/// How an actor relates to what it acts on, which is all the rules look at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Relation {
+    admin: bool,
+    reporter: bool,
+    assignee: bool,
+    author: bool,
+}
+
+impl Relation {
+    fn of(actor: &Actor, ticket: &Ticket) -> Self {
+        Self {
+            admin: actor.is_admin(),
+            reporter: ticket.reporter() == Some(actor.subject.as_str()),
+            assignee: actor.user_id.is_some() && ticket.assignee() == actor.user_id,
+            author: false,
+        }
+    }
+}
+
+/// Fails with `DomainError::Forbidden` unless `actor` may take `action` on
+/// `ticket`. Admins may do anything.
+pub fn authorize(actor: &Actor, action: Action, ticket: &Ticket) -> Result<()> {
+    check(actor, action, Relation::of(actor, ticket))
+}
+
+/// Like `authorize`, for actions on `comment`, a comment on `ticket`.
+pub fn authorize_comment(
+    actor: &Actor,
+    action: Action,
+    ticket: &Ticket,
+    comment: &Comment,
+) -> Result<()> {
+    let relation = Relation {
+        author: comment.author() == Some(actor.subject.as_str()),
+        ..Relation::of(actor, ticket)
+    };
+    check(actor, action, relation)
+}
+
+/// Like `authorize`, for actions that concern no ticket, such as managing
+/// users.
+pub fn authorize_admin(actor: &Actor, action: Action) -> Result<()> {
+    let relation = Relation {
+        admin: actor.is_admin(),
+        ..Relation::default()
+    };
+    check(actor, action, relation)
+}
+
+fn check(actor: &Actor, action: Action, relation: Relation) -> Result<()> {
+    if permits(action, relation) {
+        Ok(())
+    } else {
+        tracing::info!(actor = %actor.subject, ?action, "Forbidden");
+        Err(DomainError::Forbidden(rule(action).to_owned()))
+    }
+}
+
+fn permits(action: Action, relation: Relation) -> bool {
+    use Action::*;
+    let Relation {
+        admin,
+        reporter,
+        assignee,
+        author,
+    } = relation;
+    admin
+        || match action {
+            Edit | Label | Assign | Unassign | Reopen => reporter || assignee,
+            Start | Resolve | Close => assignee,
+            EditComment | DeleteComment => author,
+            RegisterUser | DeactivateUser => false,
+        }
+}
+
+fn rule(action: Action) -> &'static str {
+    use Action::*;
+    match action {
+        Edit | Label | Assign | Unassign | Reopen => {
+            "Only the reporter, the assignee or an admin may do this to the ticket."
+        }
+        Start | Resolve | Close => "Only the assignee or an admin may do this to the ticket.",
+        EditComment | DeleteComment => "Only the author or an admin may change the comment.",
+        RegisterUser | DeactivateUser => "Only an admin may manage users.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_matrix() {
+        use Action::*;
+        const OTHER: Relation = Relation {
+            admin: false,
+            reporter: false,
+            assignee: false,
+            author: false,
+        };
+        const ADMIN: Relation = Relation {
+            admin: true,
+            ..OTHER
+        };
+        const REPORTER: Relation = Relation {
+            reporter: true,
+            ..OTHER
+        };
+        const ASSIGNEE: Relation = Relation {
+            assignee: true,
+            ..OTHER
+        };
+        const AUTHOR: Relation = Relation {
+            author: true,
+            ..OTHER
+        };
+        #[rustfmt::skip]
+        let cases = [
+            // action,        admin, reporter, assignee, author, other
+            (Edit,           [true,  true,     true,     false,  false]),
+            (Label,          [true,  true,     true,     false,  false]),
+            (Assign,         [true,  true,     true,     false,  false]),
+            (Unassign,       [true,  true,     true,     false,  false]),
+            (Reopen,         [true,  true,     true,     false,  false]),
+            (Start,          [true,  false,    true,     false,  false]),
+            (Resolve,        [true,  false,    true,     false,  false]),
+            (Close,          [true,  false,    true,     false,  false]),
+            (EditComment,    [true,  false,    false,    true,   false]),
+            (DeleteComment,  [true,  false,    false,    true,   false]),
+            (RegisterUser,   [true,  false,    false,    false,  false]),
+            (DeactivateUser, [true,  false,    false,    false,  false]),
+        ];
+
+        let relations = [ADMIN, REPORTER, ASSIGNEE, AUTHOR, OTHER];
+
+        for (action, expected) in cases {
+            for (relation, allowed) in relations.into_iter().zip(expected) {
+                assert_eq!(
+                    permits(action, relation),
+                    allowed,
+                    "{action:?} by {relation:?}"
+                );
+            }
+        }
+    }
+}
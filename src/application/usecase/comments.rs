@@ -0,0 +1,201 @@
+use crate::application::policy::{self, Action};
+use crate::domain::actor::Actor;
+use crate::domain::clock::Clock;
+use crate::domain::comments::comment::Comment;
+use crate::domain::error::Result;
+use crate::domain::tickets::repository::{UowFactory, UowFactoryExt, UowOptions};
+use tracing::instrument;
+use uuid::Uuid;
+
+#[instrument(
+    skip(fac, clock, actor, body),
+    fields(actor = %actor.subject, ticket.id = %ticket_id)
+)]
+pub async fn add_comment(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    ticket_id: Uuid,
+    body: String,
+) -> Result<Comment> {
+    tracing::info!(ticket.id = %ticket_id, "Adding comment");
+    let now = clock.now();
+    let actor = actor.clone();
+    let comment = fac
+        .execute_with_options(UowOptions::default(), async move |uow| {
+            let ticket = uow.ticket_repo().find_by_id(ticket_id.into()).await?;
+            let comment = Comment::new(&ticket, &actor, body, now)?;
+            uow.comment_repo().insert(comment.clone()).await?;
+            Ok(comment)
+        })
+        .await?;
+    tracing::info!(ticket.id = %ticket_id, comment.id = %comment.id(), "Comment added");
+    Ok(comment)
+}
+
+#[instrument(skip(fac), fields(ticket.id = %ticket_id))]
+pub async fn list_comments(fac: &dyn UowFactory, ticket_id: Uuid) -> Result<Vec<Comment>> {
+    fac.execute_with_options(UowOptions::read_only(), async move |uow| {
+        // An unknown ticket is a 404 rather than an empty thread
+        uow.ticket_repo().find_by_id(ticket_id.into()).await?;
+        uow.comment_repo().list(ticket_id.into()).await
+    })
+    .await
+}
+
+#[instrument(skip(fac), fields(ticket.id = %ticket_id, comment.id = %comment_id))]
+pub async fn get_comment(
+    fac: &dyn UowFactory,
+    ticket_id: Uuid,
+    comment_id: Uuid,
+) -> Result<Comment> {
+    fac.execute_with_options(UowOptions::read_only(), async move |uow| {
+        uow.ticket_repo().find_by_id(ticket_id.into()).await?;
+        uow.comment_repo()
+            .find_by_id(ticket_id.into(), comment_id.into())
+            .await
+    })
+    .await
+}
+
+#[instrument(
+    skip(fac, clock, actor, body),
+    fields(actor = %actor.subject, ticket.id = %ticket_id, comment.id = %comment_id)
+)]
+pub async fn edit_comment(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    ticket_id: Uuid,
+    comment_id: Uuid,
+    body: String,
+) -> Result<Comment> {
+    tracing::info!(ticket.id = %ticket_id, comment.id = %comment_id, "Editing comment");
+    let now = clock.now();
+    let actor = actor.clone();
+    fac.execute_with_options(UowOptions::default(), async move |uow| {
+        let ticket = uow.ticket_repo().find_by_id(ticket_id.into()).await?;
+        let mut repo = uow.comment_repo();
+        let mut comment = repo.find_by_id(ticket_id.into(), comment_id.into()).await?;
+        policy::authorize_comment(&actor, Action::EditComment, &ticket, &comment)?;
+        comment.edit(&ticket, body, now)?;
+        repo.save(comment.clone()).await?;
+        Ok(comment)
+    })
+    .await
+}
+
+#[instrument(
+    skip(fac, actor),
+    fields(actor = %actor.subject, ticket.id = %ticket_id, comment.id = %comment_id)
+)]
+pub async fn delete_comment(
+    fac: &dyn UowFactory,
+    actor: &Actor,
+    ticket_id: Uuid,
+    comment_id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %ticket_id, comment.id = %comment_id, "Deleting comment");
+    let actor = actor.clone();
+    fac.execute_with_options(UowOptions::default(), async move |uow| {
+        let ticket = uow.ticket_repo().find_by_id(ticket_id.into()).await?;
+        let mut repo = uow.comment_repo();
+        let comment = repo.find_by_id(ticket_id.into(), comment_id.into()).await?;
+        policy::authorize_comment(&actor, Action::DeleteComment, &ticket, &comment)?;
+        repo.delete(ticket_id.into(), comment_id.into()).await
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::tickets::{close_ticket, create_ticket, NewTicket};
+    use crate::domain::actor::Actor;
+    use crate::domain::clock::FixedClock;
+    use crate::domain::comments::comment_error::CommentError;
+    use crate::domain::error::DomainError;
+    use crate::domain::tickets::ticket_audit::AuditContext;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+    use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+    use chrono::{TimeZone, Utc};
+
+    #[tokio::test]
+    async fn closed_tickets_reject_new_comments_but_keep_the_thread() {
+        let fac = InMemoryUowFactory::default();
+        let clock = FixedClock(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap());
+        let actor = Actor::new("tester", vec![Actor::ADMIN.to_owned()]);
+        let ticket = create_ticket(
+            &fac,
+            &clock,
+            &actor,
+            AuditContext::default(),
+            NewTicket {
+                title: "Printer is jammed".to_owned(),
+                description: "Paper stuck in tray 2".to_owned(),
+                priority: TicketPriority::default(),
+                due_date: None,
+            },
+        )
+        .await
+        .unwrap();
+        let id = ticket.id().value();
+        add_comment(&fac, &clock, &actor, id, "Tray 2 cleared".to_owned())
+            .await
+            .unwrap();
+
+        close_ticket(&fac, &clock, &actor, AuditContext::default(), id)
+            .await
+            .unwrap();
+        let rejected = add_comment(&fac, &clock, &actor, id, "Still jammed".to_owned()).await;
+
+        assert!(matches!(
+            rejected,
+            Err(DomainError::Comment(CommentError::TicketClosed))
+        ));
+        let comments = list_comments(&fac, id).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body(), "Tray 2 cleared");
+    }
+
+    #[tokio::test]
+    async fn only_the_author_or_an_admin_changes_a_comment() {
+        let fac = InMemoryUowFactory::default();
+        let clock = FixedClock(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap());
+        let author = Actor::new("carol", Vec::new());
+        let other = Actor::new("mallory", Vec::new());
+        let admin = Actor::new("tester", vec![Actor::ADMIN.to_owned()]);
+        let ticket = create_ticket(
+            &fac,
+            &clock,
+            &author,
+            AuditContext::default(),
+            NewTicket {
+                title: "Printer is jammed".to_owned(),
+                description: "Paper stuck in tray 2".to_owned(),
+                priority: TicketPriority::default(),
+                due_date: None,
+            },
+        )
+        .await
+        .unwrap();
+        let id = ticket.id().value();
+        let comment = add_comment(&fac, &clock, &author, id, "Tray 2 cleared".to_owned())
+            .await
+            .unwrap();
+        let comment_id = comment.id().value();
+
+        let edited_by_other =
+            edit_comment(&fac, &clock, &other, id, comment_id, "Spam".to_owned()).await;
+        let deleted_by_other = delete_comment(&fac, &other, id, comment_id).await;
+        let edited_by_author =
+            edit_comment(&fac, &clock, &author, id, comment_id, "Tray 3".to_owned()).await;
+
+        assert_eq!(comment.author(), Some("carol"));
+        assert!(matches!(edited_by_other, Err(DomainError::Forbidden(_))));
+        assert!(matches!(deleted_by_other, Err(DomainError::Forbidden(_))));
+        assert_eq!(edited_by_author.unwrap().body(), "Tray 3");
+        delete_comment(&fac, &admin, id, comment_id).await.unwrap();
+        assert!(list_comments(&fac, id).await.unwrap().is_empty());
+    }
+}
@@ -1 +1,3 @@
+pub mod comments;
 pub mod tickets;
+pub mod users;
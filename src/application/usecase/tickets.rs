@@ -1,53 +1,1221 @@
-use crate::domain::tickets::repository::{UowFactory, UowFactoryExt};
+use crate::application::policy::{self, Action};
+use crate::domain::actor::Actor;
+use crate::domain::clock::Clock;
+use crate::domain::error::DomainError;
+use crate::domain::idempotency::idempotency_error::IdempotencyError;
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use crate::domain::idempotency::idempotency_record::{IdempotencyRecord, StoredResponse};
+use crate::domain::tickets::repository::{
+    IsolationLevel, RetryPolicy, UnitOfWork, UowFactory, UowFactoryExt, UowOptions,
+};
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry};
 use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_id::TicketId;
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::tickets::ticket_query::{TicketPage, TicketQuery};
+use crate::domain::tickets::ticket_search::{TicketSearch, TicketSearchHit};
+use crate::domain::users::user::User;
+use crate::domain::users::user_error::UserError;
 use crate::{domain::error::Result, domain::tickets::ticket::Ticket};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
 use tracing::{instrument, Instrument};
 use uuid::Uuid;
 
-#[instrument(skip(fac), fields(ticket.id = tracing::field::Empty))]
+const LIST_STATEMENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone)]
+pub struct NewTicket {
+    pub title: String,
+    pub description: String,
+    pub priority: TicketPriority,
+    pub due_date: Option<NaiveDate>,
+}
+
+/// Changes requested by a partial update; `None` leaves a field as it is.
+#[derive(Debug, Clone, Default)]
+pub struct TicketEdit {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<TicketPriority>,
+    /// `Some(None)` clears the due date.
+    pub due_date: Option<Option<NaiveDate>>,
+}
+
+impl TicketEdit {
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.priority.is_none()
+            && self.due_date.is_none()
+    }
+
+    fn apply(self, ticket: &mut Ticket, now: DateTime<Utc>) -> Result<()> {
+        if let Some(title) = self.title {
+            ticket.change_title(title, now)?;
+        }
+        if let Some(description) = self.description {
+            ticket.change_description(description, now)?;
+        }
+        if let Some(priority) = self.priority {
+            ticket.change_priority(priority, now);
+        }
+        if let Some(due_date) = self.due_date {
+            ticket.change_due_date(due_date, now)?;
+        }
+        Ok(())
+    }
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = tracing::field::Empty)
+)]
 pub async fn create_ticket(
     fac: &dyn UowFactory,
-    title: String,
-    description: String,
-) -> Result<Uuid> {
-    tracing::info!(title = %title,"Creating ticket");
-    let ticket = Ticket::new(title, description, None)?;
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    new: NewTicket,
+) -> Result<Ticket> {
+    tracing::info!(title = %new.title, "Creating ticket");
+    let ticket = Ticket::new(
+        new.title,
+        new.description,
+        None,
+        actor,
+        new.priority,
+        new.due_date,
+        clock.now(),
+    )?;
     let ticket_id = ticket.id();
-    fac.execute_in_transaction(async move |uow| {
-        let mut repo = uow.ticket_repo();
-        repo.insert(ticket.clone()).await?;
-        Ok(())
-    })
+    let inserted = ticket.clone();
+    fac.execute_with_options(
+        UowOptions::default().with_audit(audit.with_actor(actor)),
+        async move |uow| {
+            let mut repo = uow.ticket_repo();
+            repo.insert(inserted).await?;
+            Ok(())
+        },
+    )
     .await?;
-    tracing::Span::current().record("ticket.id", &ticket_id.value().to_string());
+    tracing::Span::current().record("ticket.id", ticket_id.value().to_string());
     tracing::info!(ticket.id = %ticket_id, "Ticket created");
 
-    Ok(ticket_id.value())
+    Ok(ticket)
 }
 
-#[instrument(skip(fac), fields(ticket.id = %id))]
-pub async fn close_ticket(fac: &dyn UowFactory, id: Uuid) -> Result<()> {
+/// A create request that the client may retry under the same key.
+pub struct IdempotentRequest<F> {
+    pub key: IdempotencyKey,
+    /// Digest of everything in the request that affects its outcome.
+    pub request_hash: String,
+    /// Renders the response to store for the created ticket.
+    pub respond: F,
+}
+
+/// Creates a ticket at most once per idempotency key and actor.
+///
+/// The response is stored in the same transaction as the ticket, so a retry
+/// either replays it or finds no trace of the earlier attempt. Reusing a key
+/// for a different request fails with `IdempotencyError::KeyReused`.
+#[instrument(
+    skip(fac, clock, actor, audit, request),
+    fields(actor = %actor.subject, idempotency_key = %request.key.as_ref())
+)]
+pub async fn create_ticket_idempotently<F>(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    new: NewTicket,
+    request: IdempotentRequest<F>,
+) -> Result<StoredResponse>
+where
+    F: Fn(&Ticket) -> StoredResponse + Clone + Send + Sync + 'static,
+{
+    tracing::info!(title = %new.title, "Creating ticket");
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let IdempotentRequest {
+                key,
+                request_hash,
+                respond,
+            } = request;
+            let mut records = uow.idempotency_repo();
+            if let Some(record) = records.find(&actor.subject, &key).await? {
+                if record.request_hash != request_hash {
+                    return Err(IdempotencyError::KeyReused.into());
+                }
+                tracing::info!("Replaying response of an earlier request");
+                return Ok(record.response);
+            }
+            let ticket = Ticket::new(
+                new.title,
+                new.description,
+                None,
+                &actor,
+                new.priority,
+                new.due_date,
+                now,
+            )?;
+            uow.ticket_repo().insert(ticket.clone()).await?;
+            let response = respond(&ticket);
+            records
+                .insert(IdempotencyRecord {
+                    actor: actor.subject,
+                    key,
+                    request_hash,
+                    response: response.clone(),
+                    created_at: now,
+                })
+                .await?;
+            tracing::info!(ticket.id = %ticket.id(), "Ticket created");
+            Ok(response)
+        },
+    )
+    .await
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn close_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+) -> Result<()> {
     tracing::info!(id = %id, "Closing ticket");
-    fac.execute_in_transaction(async move |uow| {
-        let mut repo = uow.ticket_repo();
-        let span = tracing::info_span!("close_ticket_task", %id);
-        async {
-            tracing::info!(ticket.id = %id, "Finding ticket by id");
-            let mut ticket = repo
-                .find_by_id(id.into())
-                .await
-                .map_err(|_| TicketError::NotFound)?;
-            tracing::info!(ticket.id = %ticket.id(), "Closing ticket");
-            ticket.close();
-            repo.save(ticket.clone()).await?;
-            Ok(())
-        }
-        .instrument(span)
-        .await
-    })
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let mut repo = uow.ticket_repo();
+            let span = tracing::info_span!("close_ticket_task", %id);
+            async {
+                tracing::info!(ticket.id = %id, "Finding ticket by id");
+                let mut ticket = repo.find_by_id(id.into()).await?;
+                policy::authorize(&actor, Action::Close, &ticket)?;
+                tracing::info!(ticket.id = %ticket.id(), "Closing ticket");
+                ticket.close(now)?;
+                repo.save(ticket.clone()).await?;
+                Ok(())
+            }
+            .instrument(span)
+            .await
+        },
+    )
     .await?;
 
-    tracing::Span::current().record("ticket.id", &id.to_string());
+    tracing::Span::current().record("ticket.id", id.to_string());
     tracing::info!(ticket.id = %id, "Ticket closed");
     Ok(())
 }
+
+#[instrument(skip(fac), fields(ticket.id = %id))]
+pub async fn get_ticket(fac: &dyn UowFactory, id: Uuid) -> Result<Ticket> {
+    tracing::info!(ticket.id = %id, "Finding ticket by id");
+    let ticket = fac
+        .execute_with_options(UowOptions::read_only(), async move |uow| {
+            let repo = uow.ticket_repo();
+            repo.find_by_id(id.into()).await
+        })
+        .await?;
+    Ok(ticket)
+}
+
+/// Returns the audit history of a ticket, oldest change first.
+#[instrument(skip(fac), fields(ticket.id = %id))]
+pub async fn get_ticket_history(fac: &dyn UowFactory, id: Uuid) -> Result<Vec<TicketAuditEntry>> {
+    tracing::info!(ticket.id = %id, "Finding ticket history");
+    fac.execute_with_options(UowOptions::read_only(), async move |uow| {
+        let repo = uow.ticket_repo();
+        // Tickets created before auditing have no entries, unknown ids are 404
+        repo.find_by_id(id.into()).await?;
+        repo.history(id.into()).await
+    })
+    .await
+}
+
+#[instrument(skip(fac, clock))]
+pub async fn list_tickets(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    mut query: TicketQuery,
+) -> Result<TicketPage> {
+    query.limit = query.limit.clamp(1, TicketQuery::MAX_LIMIT);
+    query.as_of = clock.now().date_naive();
+    // The count and the page must see the same snapshot
+    let options = UowOptions::read_only()
+        .with_isolation_level(IsolationLevel::RepeatableRead)
+        .with_statement_timeout(LIST_STATEMENT_TIMEOUT);
+    let page = fac
+        .execute_with_options(options, async move |uow| {
+            let repo = uow.ticket_repo();
+            repo.list(&query).await
+        })
+        .await?;
+    tracing::info!(
+        count = page.items.len(),
+        total = page.total,
+        "Tickets listed"
+    );
+    Ok(page)
+}
+
+/// Finds tickets by keywords in their title or description, most relevant
+/// first.
+#[instrument(skip(fac, search), fields(text = %search.text))]
+pub async fn search_tickets(
+    fac: &dyn UowFactory,
+    mut search: TicketSearch,
+) -> Result<Vec<TicketSearchHit>> {
+    search.limit = search.limit.clamp(1, TicketSearch::MAX_LIMIT);
+    let options = UowOptions::read_only().with_statement_timeout(LIST_STATEMENT_TIMEOUT);
+    let hits = fac
+        .execute_with_options(options, async move |uow| {
+            let repo = uow.ticket_repo();
+            repo.search(&search).await
+        })
+        .await?;
+    tracing::info!(count = hits.len(), "Tickets searched");
+    Ok(hits)
+}
+
+/// Applies a partial edit, provided the client still holds `expected_version`.
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn update_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    expected_version: i64,
+    edit: TicketEdit,
+) -> Result<Ticket> {
+    tracing::info!(ticket.id = %id, expected_version, "Updating ticket");
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    let ticket = fac
+        .execute_with_options(UowOptions::default().with_audit(audit), async move |uow| {
+            let mut repo = uow.ticket_repo();
+            let mut ticket = repo.find_by_id(id.into()).await?;
+            policy::authorize(&actor, Action::Edit, &ticket)?;
+            if ticket.version() != expected_version {
+                return Err(DomainError::VersionMismatch {
+                    expected: expected_version,
+                });
+            }
+            if edit.is_empty() {
+                return Ok(ticket);
+            }
+            edit.apply(&mut ticket, now)?;
+            repo.save(ticket).await.map_err(|e| match e {
+                // Someone else saved in between, so the client's copy is stale too
+                DomainError::ConcurrentModification => DomainError::VersionMismatch {
+                    expected: expected_version,
+                },
+                e => e,
+            })?;
+            repo.find_by_id(id.into()).await
+        })
+        .await?;
+    tracing::info!(ticket.id = %id, version = ticket.version(), "Ticket updated");
+    Ok(ticket)
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id, user.id = %user_id)
+)]
+pub async fn assign_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, user.id = %user_id, "Assigning ticket");
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let mut repo = uow.ticket_repo();
+            let mut ticket = repo.find_by_id(id.into()).await?;
+            policy::authorize(&actor, Action::Assign, &ticket)?;
+            let user = find_assignee(uow.as_ref(), user_id).await?;
+            ticket.assign(&user, now)?;
+            repo.save(ticket).await?;
+            Ok(())
+        },
+    )
+    .await?;
+    tracing::info!(ticket.id = %id, user.id = %user_id, "Ticket assigned");
+    Ok(())
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn unassign_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, "Unassigning ticket");
+    change_ticket(
+        fac,
+        clock,
+        actor,
+        audit,
+        id,
+        Action::Unassign,
+        Ticket::unassign,
+    )
+    .await?;
+    tracing::info!(ticket.id = %id, "Ticket unassigned");
+    Ok(())
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn start_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, "Starting progress on ticket");
+    change_ticket(
+        fac,
+        clock,
+        actor,
+        audit,
+        id,
+        Action::Start,
+        Ticket::start_progress,
+    )
+    .await?;
+    tracing::info!(ticket.id = %id, "Ticket in progress");
+    Ok(())
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn resolve_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, "Resolving ticket");
+    change_ticket(
+        fac,
+        clock,
+        actor,
+        audit,
+        id,
+        Action::Resolve,
+        Ticket::resolve,
+    )
+    .await?;
+    tracing::info!(ticket.id = %id, "Ticket resolved");
+    Ok(())
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn reopen_ticket(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, "Reopening ticket");
+    change_ticket(fac, clock, actor, audit, id, Action::Reopen, Ticket::reopen).await?;
+    tracing::info!(ticket.id = %id, "Ticket reopened");
+    Ok(())
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn add_ticket_label(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    label: String,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, label = %label, "Adding label to ticket");
+    let add = move |ticket: &mut Ticket, now| ticket.add_label(label.clone(), now);
+    change_labels(fac, clock, actor, audit, id, add).await
+}
+
+#[instrument(
+    skip(fac, clock, actor, audit),
+    fields(actor = %actor.subject, ticket.id = %id)
+)]
+pub async fn remove_ticket_label(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    label: String,
+) -> Result<()> {
+    tracing::info!(ticket.id = %id, label = %label, "Removing label from ticket");
+    let remove = move |ticket: &mut Ticket, now| ticket.remove_label(label.clone(), now);
+    change_labels(fac, clock, actor, audit, id, remove).await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchMode {
+    /// Applies every operation or none of them.
+    #[default]
+    AllOrNothing,
+    /// Applies each operation on its own, whatever happens to the others.
+    BestEffort,
+}
+
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Close {
+        id: Uuid,
+    },
+    Assign {
+        id: Uuid,
+        user_id: Uuid,
+    },
+    Update {
+        id: Uuid,
+        expected_version: i64,
+        edit: TicketEdit,
+    },
+}
+
+impl BatchOperation {
+    pub const MAX_PER_BATCH: usize = 100;
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            BatchOperation::Close { id }
+            | BatchOperation::Assign { id, .. }
+            | BatchOperation::Update { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BatchOutcome {
+    Applied(Ticket),
+    Failed(DomainError),
+    /// Succeeded on its own, but was undone because another operation failed.
+    RolledBack,
+}
+
+/// Runs operations on several tickets, returning one outcome per operation
+/// in the order given.
+///
+/// In `AllOrNothing` mode all tickets are loaded and saved together in one
+/// transaction, and nothing is saved if any operation fails. Operations on
+/// the same ticket see the changes of the ones before them, and as the ticket
+/// is then saved once, an update following another change must expect the
+/// version after the loaded one.
+#[instrument(
+    skip(fac, clock, actor, audit, operations),
+    fields(actor = %actor.subject, operations = operations.len())
+)]
+pub async fn run_ticket_batch(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    mode: BatchMode,
+    operations: Vec<BatchOperation>,
+) -> Result<Vec<BatchOutcome>> {
+    tracing::info!(?mode, "Running ticket batch");
+    let outcomes = match mode {
+        BatchMode::AllOrNothing => run_all_or_nothing(fac, clock, actor, audit, operations).await?,
+        BatchMode::BestEffort => run_best_effort(fac, clock, actor, audit, operations).await,
+    };
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| !matches!(outcome, BatchOutcome::Applied(_)))
+        .count();
+    tracing::info!(failed, "Ticket batch finished");
+    Ok(outcomes)
+}
+
+async fn run_all_or_nothing(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    operations: Vec<BatchOperation>,
+) -> Result<Vec<BatchOutcome>> {
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let ids = operations
+                .iter()
+                .map(|op| TicketId::from(op.id()))
+                .collect::<Vec<_>>();
+            let mut repo = uow.ticket_repo();
+            let mut tickets = repo
+                .find_many(&ids)
+                .await?
+                .into_iter()
+                .map(|ticket| (ticket.id(), ticket))
+                .collect::<HashMap<_, _>>();
+
+            let mut results = Vec::with_capacity(operations.len());
+            let mut changed = Vec::new();
+            for op in operations {
+                let id = TicketId::from(op.id());
+                let result = match tickets.get_mut(&id) {
+                    Some(ticket) => {
+                        let version = ticket.version() + i64::from(changed.contains(&id));
+                        apply_batch_operation(uow.as_ref(), &actor, ticket, version, op, now).await
+                    }
+                    None => Err(TicketError::NotFound.into()),
+                };
+                if let Ok(true) = result
+                    && !changed.contains(&id)
+                {
+                    changed.push(id);
+                }
+                results.push(result);
+            }
+
+            if results.iter().any(Result::is_err) {
+                return Ok(results
+                    .into_iter()
+                    .map(|result| match result {
+                        Ok(_) => BatchOutcome::RolledBack,
+                        Err(e) => BatchOutcome::Failed(e),
+                    })
+                    .collect());
+            }
+            let changed = changed.iter().filter_map(|id| tickets.remove(id)).collect();
+            repo.save_many(changed).await?;
+            let saved = repo
+                .find_many(&ids)
+                .await?
+                .into_iter()
+                .map(|ticket| (ticket.id(), ticket))
+                .collect::<HashMap<_, _>>();
+            Ok(ids
+                .iter()
+                .map(|id| match saved.get(id) {
+                    Some(ticket) => BatchOutcome::Applied(ticket.clone()),
+                    None => BatchOutcome::Failed(TicketError::NotFound.into()),
+                })
+                .collect())
+        },
+    )
+    .await
+}
+
+/// Applies one operation to a loaded ticket at `version`, returning whether
+/// it changed.
+async fn apply_batch_operation(
+    uow: &dyn UnitOfWork,
+    actor: &Actor,
+    ticket: &mut Ticket,
+    version: i64,
+    operation: BatchOperation,
+    now: DateTime<Utc>,
+) -> Result<bool> {
+    match operation {
+        BatchOperation::Close { .. } => {
+            policy::authorize(actor, Action::Close, ticket)?;
+            ticket.close(now)?;
+        }
+        BatchOperation::Assign { user_id, .. } => {
+            policy::authorize(actor, Action::Assign, ticket)?;
+            let user = find_assignee(uow, user_id).await?;
+            ticket.assign(&user, now)?;
+        }
+        BatchOperation::Update {
+            expected_version,
+            edit,
+            ..
+        } => {
+            policy::authorize(actor, Action::Edit, ticket)?;
+            if version != expected_version {
+                return Err(DomainError::VersionMismatch {
+                    expected: expected_version,
+                });
+            }
+            if edit.is_empty() {
+                return Ok(false);
+            }
+            edit.apply(ticket, now)?;
+        }
+    }
+    Ok(true)
+}
+
+async fn run_best_effort(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    operations: Vec<BatchOperation>,
+) -> Vec<BatchOutcome> {
+    let mut outcomes = Vec::with_capacity(operations.len());
+    for op in operations {
+        let audit = audit.clone();
+        let outcome = match op {
+            BatchOperation::Close { id } => {
+                match close_ticket(fac, clock, actor, audit, id).await {
+                    Ok(()) => get_ticket(fac, id).await,
+                    Err(e) => Err(e),
+                }
+            }
+            BatchOperation::Assign { id, user_id } => {
+                match assign_ticket(fac, clock, actor, audit, id, user_id).await {
+                    Ok(()) => get_ticket(fac, id).await,
+                    Err(e) => Err(e),
+                }
+            }
+            BatchOperation::Update {
+                id,
+                expected_version,
+                edit,
+            } => update_ticket(fac, clock, actor, audit, id, expected_version, edit).await,
+        };
+        outcomes.push(match outcome {
+            Ok(ticket) => BatchOutcome::Applied(ticket),
+            Err(e) => BatchOutcome::Failed(e),
+        });
+    }
+    outcomes
+}
+
+async fn find_assignee(uow: &dyn UnitOfWork, user_id: Uuid) -> Result<User> {
+    uow.user_repo()
+        .find_by_id(user_id.into())
+        .await
+        .map_err(|e| match e {
+            // The ticket exists, it is the requested assignee that does not
+            DomainError::User(UserError::NotFound) => TicketError::UnknownAssignee.into(),
+            e => e,
+        })
+}
+
+/// Like `change_ticket`, but leaves the ticket untouched when the labels
+/// already were as requested, so repeating a request creates no new version.
+async fn change_labels<F>(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    change: F,
+) -> Result<()>
+where
+    F: Fn(&mut Ticket, DateTime<Utc>) -> std::result::Result<bool, TicketError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let mut repo = uow.ticket_repo();
+            let mut ticket = repo.find_by_id(id.into()).await?;
+            policy::authorize(&actor, Action::Label, &ticket)?;
+            if change(&mut ticket, now)? {
+                repo.save(ticket).await?;
+            }
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Loads a ticket, checks the actor may take `action` on it, then applies a
+/// status change and saves it in one transaction, reloading and reapplying the
+/// change when another request got there first.
+async fn change_ticket<F>(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    audit: AuditContext,
+    id: Uuid,
+    action: Action,
+    change: F,
+) -> Result<()>
+where
+    F: Fn(&mut Ticket, DateTime<Utc>) -> std::result::Result<(), TicketError>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    let now = clock.now();
+    let audit = audit.with_actor(actor);
+    let actor = actor.clone();
+    fac.execute_with_retry(
+        UowOptions::default().with_audit(audit),
+        RetryPolicy::default(),
+        async move |uow| {
+            let mut repo = uow.ticket_repo();
+            let mut ticket = repo.find_by_id(id.into()).await?;
+            policy::authorize(&actor, action, &ticket)?;
+            change(&mut ticket, now)?;
+            repo.save(ticket).await?;
+            Ok(())
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::users::{deactivate_user, register_user};
+    use crate::domain::clock::FixedClock;
+    use crate::domain::tickets::ticket_status::TicketStatus;
+    use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+    use crate::test_fixtures::{actor, at, create};
+
+    #[tokio::test]
+    async fn changes_move_updated_at_but_not_created_at() {
+        let fac = InMemoryUowFactory::default();
+        let id = create(&fac, &at(9)).await;
+        let user = register_user(&fac, &at(9), &actor(), "Alice".to_owned())
+            .await
+            .unwrap();
+
+        assign_ticket(
+            &fac,
+            &at(10),
+            &actor(),
+            AuditContext::default(),
+            id,
+            user.id().value(),
+        )
+        .await
+        .unwrap();
+
+        let timestamps = get_ticket(&fac, id).await.unwrap().timestamps();
+        assert_eq!(timestamps.created_at, at(9).0);
+        assert_eq!(timestamps.updated_at, at(10).0);
+        assert_eq!(timestamps.closed_at, None);
+    }
+
+    #[tokio::test]
+    async fn closing_sets_closed_at_and_reopening_clears_it() {
+        let fac = InMemoryUowFactory::default();
+        let id = create(&fac, &at(9)).await;
+
+        close_ticket(&fac, &at(11), &actor(), AuditContext::default(), id)
+            .await
+            .unwrap();
+        let closed = get_ticket(&fac, id).await.unwrap().timestamps();
+        assert_eq!(closed.closed_at, Some(at(11).0));
+
+        reopen_ticket(&fac, &at(12), &actor(), AuditContext::default(), id)
+            .await
+            .unwrap();
+        let reopened = get_ticket(&fac, id).await.unwrap().timestamps();
+        assert_eq!(reopened.closed_at, None);
+        assert_eq!(reopened.updated_at, at(12).0);
+    }
+
+    #[tokio::test]
+    async fn tickets_are_only_assigned_to_active_users() {
+        let fac = InMemoryUowFactory::default();
+        let id = create(&fac, &at(9)).await;
+        let user = register_user(&fac, &at(9), &actor(), "Bob".to_owned())
+            .await
+            .unwrap();
+        deactivate_user(&fac, &actor(), user.id().value())
+            .await
+            .unwrap();
+
+        let unknown = assign_ticket(
+            &fac,
+            &at(10),
+            &actor(),
+            AuditContext::default(),
+            id,
+            Uuid::nil(),
+        )
+        .await;
+        let inactive = assign_ticket(
+            &fac,
+            &at(10),
+            &actor(),
+            AuditContext::default(),
+            id,
+            user.id().value(),
+        )
+        .await;
+
+        assert!(matches!(
+            unknown,
+            Err(DomainError::Ticket(TicketError::UnknownAssignee))
+        ));
+        assert!(matches!(
+            inactive,
+            Err(DomainError::Ticket(TicketError::InactiveAssignee))
+        ));
+        assert_eq!(get_ticket(&fac, id).await.unwrap().assignee(), None);
+    }
+
+    #[tokio::test]
+    async fn tickets_past_their_due_date_are_listed_as_overdue_until_resolved() {
+        let fac = InMemoryUowFactory::default();
+        let due = at(9).0.date_naive();
+        let ticket = create_ticket(
+            &fac,
+            &at(9),
+            &actor(),
+            AuditContext::default(),
+            NewTicket {
+                title: "Renew certificate".to_owned(),
+                description: "Expires at the end of the day".to_owned(),
+                priority: TicketPriority::High,
+                due_date: Some(due),
+            },
+        )
+        .await
+        .unwrap();
+        let id = ticket.id().value();
+        let next_day = FixedClock(at(9).0 + chrono::Duration::days(1));
+        let overdue = || TicketQuery {
+            overdue: Some(true),
+            limit: TicketQuery::DEFAULT_LIMIT,
+            ..TicketQuery::default()
+        };
+
+        assert_eq!(
+            list_tickets(&fac, &at(23), overdue()).await.unwrap().total,
+            0
+        );
+        let page = list_tickets(&fac, &next_day, overdue()).await.unwrap();
+        assert_eq!(page.items[0].id().value(), id);
+
+        close_ticket(&fac, &next_day, &actor(), AuditContext::default(), id)
+            .await
+            .unwrap();
+        assert_eq!(
+            list_tickets(&fac, &next_day, overdue())
+                .await
+                .unwrap()
+                .total,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn reporters_edit_their_tickets_but_only_the_assignee_closes_them() {
+        let fac = InMemoryUowFactory::default();
+        let reporter = Actor::new("carol", Vec::new());
+        let ticket = create_ticket(
+            &fac,
+            &at(9),
+            &reporter,
+            AuditContext::default(),
+            NewTicket {
+                title: "Printer is jammed".to_owned(),
+                description: "Paper stuck in tray 2".to_owned(),
+                priority: TicketPriority::default(),
+                due_date: None,
+            },
+        )
+        .await
+        .unwrap();
+        let id = ticket.id().value();
+        let user = register_user(&fac, &at(9), &actor(), "Dave".to_owned())
+            .await
+            .unwrap();
+        let assignee = Actor::new(user.id().value().to_string(), Vec::new());
+        let edit = TicketEdit {
+            priority: Some(TicketPriority::High),
+            ..TicketEdit::default()
+        };
+
+        let stranger = Actor::new("mallory", Vec::new());
+        let rejected = update_ticket(
+            &fac,
+            &at(10),
+            &stranger,
+            AuditContext::default(),
+            id,
+            0,
+            edit.clone(),
+        )
+        .await;
+        update_ticket(
+            &fac,
+            &at(10),
+            &reporter,
+            AuditContext::default(),
+            id,
+            0,
+            edit,
+        )
+        .await
+        .unwrap();
+        assign_ticket(
+            &fac,
+            &at(10),
+            &reporter,
+            AuditContext::default(),
+            id,
+            user.id().value(),
+        )
+        .await
+        .unwrap();
+        let close_by_reporter =
+            close_ticket(&fac, &at(11), &reporter, AuditContext::default(), id).await;
+        close_ticket(&fac, &at(11), &assignee, AuditContext::default(), id)
+            .await
+            .unwrap();
+
+        assert!(matches!(rejected, Err(DomainError::Forbidden(_))));
+        assert!(matches!(close_by_reporter, Err(DomainError::Forbidden(_))));
+        let ticket = get_ticket(&fac, id).await.unwrap();
+        assert_eq!(ticket.reporter(), Some("carol"));
+        assert_eq!(ticket.status(), TicketStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn retries_with_the_same_idempotency_key_create_one_ticket() {
+        let fac = InMemoryUowFactory::default();
+        let new = NewTicket {
+            title: "Printer is jammed".to_owned(),
+            description: "Paper stuck in tray 2".to_owned(),
+            priority: TicketPriority::default(),
+            due_date: None,
+        };
+        let request = |request_hash: &str| IdempotentRequest {
+            key: IdempotencyKey::try_from("retry-1").unwrap(),
+            request_hash: request_hash.to_owned(),
+            respond: |ticket: &Ticket| StoredResponse {
+                status: 201,
+                headers: Vec::new(),
+                body: Some(ticket.id().to_string()),
+            },
+        };
+        let (clock, actor) = (at(9), actor());
+        let create = |request_hash| {
+            create_ticket_idempotently(
+                &fac,
+                &clock,
+                &actor,
+                AuditContext::default(),
+                new.clone(),
+                request(request_hash),
+            )
+        };
+
+        let first = create("a").await.unwrap();
+        let retry = create("a").await.unwrap();
+        let reused = create("b").await;
+
+        assert_eq!(retry, first);
+        assert!(matches!(
+            reused,
+            Err(DomainError::Idempotency(IdempotencyError::KeyReused))
+        ));
+        let all = TicketQuery {
+            limit: TicketQuery::DEFAULT_LIMIT,
+            ..TicketQuery::default()
+        };
+        let page = list_tickets(&fac, &at(9), all).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(Some(page.items[0].id().to_string()), first.body);
+    }
+
+    #[tokio::test]
+    async fn failed_batch_operations_undo_the_others_only_when_all_or_nothing() {
+        let fac = InMemoryUowFactory::default();
+        let first = create(&fac, &at(9)).await;
+        let second = create(&fac, &at(9)).await;
+        let operations = vec![
+            BatchOperation::Close { id: first },
+            BatchOperation::Assign {
+                id: second,
+                user_id: Uuid::new_v4(),
+            },
+        ];
+        let run = |mode| {
+            let operations = operations.clone();
+            let clock = at(10);
+            let actor = actor();
+            let fac = &fac;
+            async move {
+                run_ticket_batch(
+                    fac,
+                    &clock,
+                    &actor,
+                    AuditContext::default(),
+                    mode,
+                    operations,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let outcomes = run(BatchMode::AllOrNothing).await;
+        assert!(matches!(outcomes[0], BatchOutcome::RolledBack));
+        assert!(matches!(
+            outcomes[1],
+            BatchOutcome::Failed(DomainError::Ticket(TicketError::UnknownAssignee))
+        ));
+        let untouched = get_ticket(&fac, first).await.unwrap();
+        assert_eq!(untouched.status(), TicketStatus::Open);
+
+        let outcomes = run(BatchMode::BestEffort).await;
+        assert!(
+            matches!(&outcomes[0], BatchOutcome::Applied(t) if t.status() == TicketStatus::Closed)
+        );
+        assert!(matches!(outcomes[1], BatchOutcome::Failed(_)));
+        let closed = get_ticket(&fac, first).await.unwrap();
+        assert_eq!(closed.status(), TicketStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn batched_updates_of_one_ticket_expect_the_version_left_by_the_ones_before() {
+        let fac = InMemoryUowFactory::default();
+        let id = create(&fac, &at(9)).await;
+        let update = |expected_version, title: &str| BatchOperation::Update {
+            id,
+            expected_version,
+            edit: TicketEdit {
+                title: Some(title.to_owned()),
+                ..TicketEdit::default()
+            },
+        };
+        let (clock, actor) = (at(10), actor());
+        let run = |operations| {
+            run_ticket_batch(
+                &fac,
+                &clock,
+                &actor,
+                AuditContext::default(),
+                BatchMode::AllOrNothing,
+                operations,
+            )
+        };
+
+        let stale = run(vec![update(0, "Tray 2 jammed"), update(0, "Tray 3 jammed")])
+            .await
+            .unwrap();
+        assert!(matches!(stale[0], BatchOutcome::RolledBack));
+        assert!(matches!(
+            stale[1],
+            BatchOutcome::Failed(DomainError::VersionMismatch { expected: 0 })
+        ));
+        assert_eq!(get_ticket(&fac, id).await.unwrap().version(), 0);
+
+        let chained = run(vec![update(0, "Tray 2 jammed"), update(1, "Tray 3 jammed")])
+            .await
+            .unwrap();
+        assert!(chained
+            .iter()
+            .all(|o| matches!(o, BatchOutcome::Applied(_))));
+        let ticket = get_ticket(&fac, id).await.unwrap();
+        assert_eq!(
+            (ticket.title().as_str(), ticket.version()),
+            ("Tray 3 jammed", 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn saves_record_the_previous_and_new_values_with_the_actor_and_trace_id() {
+        let fac = InMemoryUowFactory::default();
+        let id = create(&fac, &at(9)).await;
+        let editor = Actor::new("editor", vec![Actor::ADMIN.to_owned()]);
+        let audit = AuditContext {
+            actor: None,
+            trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736".to_owned()),
+        };
+
+        update_ticket(
+            &fac,
+            &at(10),
+            &editor,
+            audit,
+            id,
+            0,
+            TicketEdit {
+                title: Some("Printer is on fire".to_owned()),
+                priority: Some(TicketPriority::High),
+                ..TicketEdit::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let history = get_ticket_history(&fac, id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        let created = &history[0];
+        assert!(created.previous.is_none());
+        assert_eq!(created.actor.as_deref(), Some("tester"));
+        let saved = &history[1];
+        let previous = saved.previous.as_ref().unwrap();
+        assert_eq!(previous.title, "Printer is jammed");
+        assert_eq!(previous.priority, TicketPriority::Medium);
+        assert_eq!(previous.version, 0);
+        assert_eq!(saved.current.title, "Printer is on fire");
+        assert_eq!(saved.current.priority, TicketPriority::High);
+        assert_eq!(saved.current.version, 1);
+        assert_eq!(saved.current.description, previous.description);
+        assert_eq!(saved.actor.as_deref(), Some("editor"));
+        assert_eq!(
+            saved.trace_id.as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::application::policy::{self, Action};
+use crate::domain::actor::Actor;
+use crate::domain::clock::Clock;
+use crate::domain::error::Result;
+use crate::domain::tickets::repository::{UowFactory, UowFactoryExt, UowOptions};
+use crate::domain::users::user::User;
+use tracing::instrument;
+use uuid::Uuid;
+
+#[instrument(
+    skip(fac, clock, actor),
+    fields(actor = %actor.subject, user.id = tracing::field::Empty)
+)]
+pub async fn register_user(
+    fac: &dyn UowFactory,
+    clock: &dyn Clock,
+    actor: &Actor,
+    name: String,
+) -> Result<User> {
+    policy::authorize_admin(actor, Action::RegisterUser)?;
+    let user = User::new(name, clock.now())?;
+    let inserted = user.clone();
+    fac.execute_with_options(UowOptions::default(), async move |uow| {
+        uow.user_repo().insert(inserted).await
+    })
+    .await?;
+    tracing::Span::current().record("user.id", user.id().to_string());
+    tracing::info!(user.id = %user.id(), "User registered");
+    Ok(user)
+}
+
+#[instrument(skip(fac), fields(user.id = %id))]
+pub async fn get_user(fac: &dyn UowFactory, id: Uuid) -> Result<User> {
+    fac.execute_with_options(UowOptions::read_only(), async move |uow| {
+        uow.user_repo().find_by_id(id.into()).await
+    })
+    .await
+}
+
+/// Stops the user from being assigned new tickets; tickets already assigned
+/// to them stay as they are.
+#[instrument(skip(fac, actor), fields(actor = %actor.subject, user.id = %id))]
+pub async fn deactivate_user(fac: &dyn UowFactory, actor: &Actor, id: Uuid) -> Result<User> {
+    policy::authorize_admin(actor, Action::DeactivateUser)?;
+    let user = fac
+        .execute_with_options(UowOptions::default(), async move |uow| {
+            let mut repo = uow.user_repo();
+            let mut user = repo.find_by_id(id.into()).await?;
+            user.deactivate();
+            repo.save(user.clone()).await?;
+            Ok(user)
+        })
+        .await?;
+    tracing::info!(user.id = %id, "User deactivated");
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock::FixedClock;
+    use crate::domain::error::DomainError;
+    use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+    use chrono::{TimeZone, Utc};
+
+    #[tokio::test]
+    async fn only_admins_manage_users() {
+        let fac = InMemoryUowFactory::default();
+        let clock = FixedClock(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap());
+        let admin = Actor::new("tester", vec![Actor::ADMIN.to_owned()]);
+        let member = Actor::new("mallory", Vec::new());
+        let user = register_user(&fac, &clock, &admin, "Alice".to_owned())
+            .await
+            .unwrap();
+
+        let registered = register_user(&fac, &clock, &member, "Eve".to_owned()).await;
+        let deactivated = deactivate_user(&fac, &member, user.id().value()).await;
+
+        assert!(matches!(registered, Err(DomainError::Forbidden(_))));
+        assert!(matches!(deactivated, Err(DomainError::Forbidden(_))));
+        let user = get_user(&fac, user.id().value()).await.unwrap();
+        assert!(user.is_active());
+    }
+}
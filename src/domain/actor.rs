@@ -0,0 +1,35 @@
+use crate::domain::users::user_id::UserId;
+
+/// Who is making a request, as established by authentication.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    /// Stable name of the caller: the `sub` claim of a token, or the name of
+    /// an API key.
+    pub subject: String,
+    /// The user the caller acts as, when the subject is a user id.
+    pub user_id: Option<UserId>,
+    pub roles: Vec<String>,
+}
+
+impl Actor {
+    /// Role that may act on any ticket.
+    pub const ADMIN: &'static str = "admin";
+
+    pub fn new(subject: impl Into<String>, roles: Vec<String>) -> Self {
+        let subject = subject.into();
+        let user_id = uuid::Uuid::parse_str(&subject).ok().map(UserId::from);
+        Self {
+            subject,
+            user_id,
+            roles,
+        }
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.has_role(Self::ADMIN)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::domain::actor::Actor;
+use crate::domain::comments::comment_body::{CommentBody, CommentBodyError};
+use crate::domain::comments::comment_error::CommentError;
+use crate::domain::comments::comment_id::CommentId;
+use crate::domain::tickets::ticket::{Ticket, TicketId};
+use crate::domain::tickets::ticket_status::TicketStatus;
+use chrono::{DateTime, Utc};
+
+/// A message in the discussion thread of a ticket.
+///
+/// The thread of a closed ticket is frozen: comments can no longer be added
+/// or edited, but they can still be deleted, e.g. to remove spam.
+#[derive(Debug, Clone)]
+pub struct Comment {
+    id: CommentId,
+    ticket_id: TicketId,
+    /// Subject of the actor who wrote it; `None` for comments written before
+    /// authors were recorded.
+    author: Option<String>,
+    body: CommentBody,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Comment {
+    pub(crate) fn reconstruct(
+        id: CommentId,
+        ticket_id: TicketId,
+        author: Option<String>,
+        body: impl TryInto<CommentBody, Error = CommentBodyError>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            ticket_id,
+            author,
+            body: body.try_into().unwrap(),
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn new(
+        ticket: &Ticket,
+        author: &Actor,
+        body: impl TryInto<CommentBody, Error = CommentBodyError>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, CommentError> {
+        Self::ensure_open(ticket)?;
+        Ok(Self {
+            id: CommentId::new(),
+            ticket_id: ticket.id(),
+            author: Some(author.subject.clone()),
+            body: body.try_into()?,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replaces the body; `ticket` is the ticket the comment belongs to.
+    pub fn edit(
+        &mut self,
+        ticket: &Ticket,
+        body: impl TryInto<CommentBody, Error = CommentBodyError>,
+        now: DateTime<Utc>,
+    ) -> Result<(), CommentError> {
+        Self::ensure_open(ticket)?;
+        self.body = body.try_into()?;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    fn ensure_open(ticket: &Ticket) -> Result<(), CommentError> {
+        if ticket.status() == TicketStatus::Closed {
+            return Err(CommentError::TicketClosed);
+        }
+        Ok(())
+    }
+
+    pub fn id(&self) -> CommentId {
+        self.id
+    }
+
+    pub fn ticket_id(&self) -> TicketId {
+        self.ticket_id
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    pub fn body(&self) -> String {
+        self.body.as_ref().to_string()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
@@ -0,0 +1,8 @@
+use nutype::nutype;
+
+#[nutype(
+    sanitize(trim),
+    validate(not_empty, len_char_max = 2000),
+    derive(Debug, Clone, PartialEq, Eq, AsRef, TryFrom)
+)]
+pub struct CommentBody(String);
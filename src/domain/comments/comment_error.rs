@@ -0,0 +1,12 @@
+use crate::domain::comments::comment_body::CommentBodyError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CommentError {
+    #[error("Comment not found")]
+    NotFound,
+    #[error("Closed tickets cannot be commented on")]
+    TicketClosed,
+    #[error("Comment body error: {0}")]
+    CommentBodyError(#[from] CommentBodyError),
+}
@@ -0,0 +1,5 @@
+pub mod comment;
+pub mod comment_body;
+pub mod comment_error;
+pub mod comment_id;
+pub mod repository;
\ No newline at end of file
@@ -0,0 +1,17 @@
+use crate::domain::comments::comment::Comment;
+use crate::domain::comments::comment_id::CommentId;
+use crate::domain::error::Result;
+use crate::domain::tickets::ticket_id::TicketId;
+use async_trait::async_trait;
+
+/// Comments are always looked up through their ticket, so a comment id used
+/// under another ticket is not found.
+#[async_trait]
+pub trait CommentRepository: Send + Sync {
+    async fn find_by_id(&self, ticket_id: TicketId, id: CommentId) -> Result<Comment>;
+    /// Comments of a ticket, oldest first.
+    async fn list(&self, ticket_id: TicketId) -> Result<Vec<Comment>>;
+    async fn insert(&mut self, comment: Comment) -> Result<()>;
+    async fn save(&mut self, comment: Comment) -> Result<()>;
+    async fn delete(&mut self, ticket_id: TicketId, id: CommentId) -> Result<()>;
+}
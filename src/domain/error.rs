@@ -1,18 +1,43 @@
+use crate::domain::comments::comment_error::CommentError;
+use crate::domain::idempotency::idempotency_error::IdempotencyError;
 use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::users::user_error::UserError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum DomainError {
     #[error("Ticket error: {0}")]
     Ticket(#[from] TicketError),
+    #[error("Comment error: {0}")]
+    Comment(#[from] CommentError),
+    #[error("User error: {0}")]
+    User(#[from] UserError),
+    #[error("Idempotency error: {0}")]
+    Idempotency(#[from] IdempotencyError),
     #[error("Repository error: {0}")]
     RepositoryError(String),
     #[error("Concurrent modification error")]
     ConcurrentModification,
+    #[error("Transaction conflict: {0}")]
+    TransactionConflict(String),
+    #[error("Version mismatch: expected version {expected}")]
+    VersionMismatch { expected: i64 },
     #[error("Infrastructure error: {0}")]
     Infrastructure(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("Invalid ticket id")]
     InvalidTicketId,
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+}
+
+impl DomainError {
+    /// Whether running the same unit of work again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            DomainError::ConcurrentModification | DomainError::TransactionConflict(_)
+        )
+    }
 }
 
 pub type Result<T> = std::result::Result<T, DomainError>;
@@ -0,0 +1,10 @@
+use crate::domain::idempotency::idempotency_key::IdempotencyKeyError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error("The idempotency key was already used for a different request")]
+    KeyReused,
+    #[error("Idempotency key error: {0}")]
+    IdempotencyKeyError(#[from] IdempotencyKeyError),
+}
@@ -0,0 +1,13 @@
+use nutype::nutype;
+
+/// Client-chosen key that marks retries of the same request.
+#[nutype(
+    sanitize(trim),
+    validate(
+        not_empty,
+        len_char_max = 255,
+        predicate = |key| key.chars().all(|c| c.is_ascii_graphic())
+    ),
+    derive(Debug, Clone, PartialEq, Eq, Hash, AsRef, TryFrom)
+)]
+pub struct IdempotencyKey(String);
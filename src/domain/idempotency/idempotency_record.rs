@@ -0,0 +1,24 @@
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use chrono::{DateTime, Utc};
+
+/// The outcome of a request made under an idempotency key, kept so that
+/// retries get the same response instead of repeating the request.
+///
+/// Keys are scoped to the actor that sent them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    pub actor: String,
+    pub key: IdempotencyKey,
+    /// Digest of the request, to tell retries from other requests that reuse
+    /// the key.
+    pub request_hash: String,
+    pub response: StoredResponse,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
@@ -0,0 +1,4 @@
+pub mod idempotency_error;
+pub mod idempotency_key;
+pub mod idempotency_record;
+pub mod repository;
\ No newline at end of file
@@ -0,0 +1,12 @@
+use crate::domain::error::Result;
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use crate::domain::idempotency::idempotency_record::IdempotencyRecord;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    async fn find(&self, actor: &str, key: &IdempotencyKey) -> Result<Option<IdempotencyRecord>>;
+    /// Fails with a retryable error when a concurrent request stored the same
+    /// key first, so that running the request again replays its response.
+    async fn insert(&mut self, record: IdempotencyRecord) -> Result<()>;
+}
@@ -1,2 +1,7 @@
 pub mod tickets;
-pub mod error;
\ No newline at end of file
+pub mod error;
+pub mod clock;
+pub mod comments;
+pub mod users;
+pub mod actor;
+pub mod idempotency;
\ No newline at end of file
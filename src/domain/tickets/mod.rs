@@ -4,4 +4,12 @@ pub mod ticket;
 pub mod repository;
 pub mod ticket_title;
 pub mod ticket_description;
-pub mod ticket_error;
\ No newline at end of file
+pub mod ticket_error;
+pub mod ticket_query;
+pub mod ticket_search;
+pub mod ticket_event;
+pub mod ticket_audit;
+pub mod ticket_timestamps;
+pub mod ticket_priority;
+pub mod ticket_due_date;
+pub mod ticket_label;
\ No newline at end of file
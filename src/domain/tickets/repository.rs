@@ -1,26 +1,64 @@
+use crate::domain::comments::repository::CommentRepository;
 use crate::domain::error::{DomainError, Result};
+use crate::domain::idempotency::repository::IdempotencyRepository;
 use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry};
+use crate::domain::tickets::ticket_error::TicketError;
 use crate::domain::tickets::ticket_id::TicketId;
+use crate::domain::tickets::ticket_query::{TicketPage, TicketQuery};
+use crate::domain::tickets::ticket_search::{TicketSearch, TicketSearchHit};
+use crate::domain::users::repository::UserRepository;
 use async_trait::async_trait;
 use std::any::Any;
 use std::pin::Pin;
+use std::time::Duration;
 
 #[async_trait]
 pub trait TicketRepository: Send + Sync {
     async fn find_by_id(&self, id: TicketId) -> Result<Ticket>;
     async fn insert(&mut self, ticket: Ticket) -> Result<()>;
     async fn save(&mut self, ticket: Ticket) -> Result<()>;
+    async fn list(&self, query: &TicketQuery) -> Result<TicketPage>;
+    /// The `search.limit` most relevant matches, most relevant first.
+    async fn search(&self, search: &TicketSearch) -> Result<Vec<TicketSearchHit>>;
+    /// Audit entries written by `insert` and `save`, oldest first.
+    async fn history(&self, id: TicketId) -> Result<Vec<TicketAuditEntry>>;
+
+    /// Loads those of the tickets that exist, in no particular order.
+    async fn find_many(&self, ids: &[TicketId]) -> Result<Vec<Ticket>> {
+        let mut tickets = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.find_by_id(*id).await {
+                Ok(ticket) => tickets.push(ticket),
+                Err(DomainError::Ticket(TicketError::NotFound)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(tickets)
+    }
+
+    /// Saves several tickets, failing with `ConcurrentModification` if any of
+    /// them is no longer at the version it was loaded at.
+    async fn save_many(&mut self, tickets: Vec<Ticket>) -> Result<()> {
+        for ticket in tickets {
+            self.save(ticket).await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 pub trait UnitOfWork: Send + Sync {
     fn ticket_repo(&self) -> Box<dyn TicketRepository + '_>;
+    fn comment_repo(&self) -> Box<dyn CommentRepository + '_>;
+    fn user_repo(&self) -> Box<dyn UserRepository + '_>;
+    fn idempotency_repo(&self) -> Box<dyn IdempotencyRepository + '_>;
     async fn commit(self: Box<Self>) -> Result<()>;
 }
 
 #[async_trait]
 pub trait UowFactory: Send + Sync {
-    async fn execute_raw(&self, f: UowFnc) -> Result<Box<dyn Any + Send>>;
+    async fn execute_raw(&self, options: UowOptions, f: UowFnc) -> Result<Box<dyn Any + Send>>;
 }
 
 pub type UowFnc = Box<
@@ -32,23 +70,228 @@ pub type UowFnc = Box<
 
 #[async_trait]
 pub trait UowFactoryExt: UowFactory {
-    async fn execute_in_transaction<T, F, Fut>(&self, f: F) -> Result<T>
+    async fn execute_with_options<T, F, Fut>(&self, options: UowOptions, f: F) -> Result<T>
     where
         T: Send + 'static,
         F: FnOnce(Box<dyn UnitOfWork>) -> Fut + Send + 'static,
         Fut: Future<Output = Result<T>> + Send,
     {
         let result = self
-            .execute_raw(Box::new(|uow| {
-                Box::pin(async move {
-                    let res = f(uow).await?;
-                    Ok(Box::new(res) as Box<dyn Any + Send>)
-                })
-            }))
+            .execute_raw(
+                options,
+                Box::new(|uow| {
+                    Box::pin(async move {
+                        let res = f(uow).await?;
+                        Ok(Box::new(res) as Box<dyn Any + Send>)
+                    })
+                }),
+            )
             .await?;
         Ok(*result
             .downcast::<T>()
             .map_err(|_| DomainError::Infrastructure("Downcast failed".into()))?)
     }
+
+    /// Runs `f` in a transaction, running it again in a fresh transaction
+    /// when it fails with a retryable error such as a concurrent modification.
+    ///
+    /// `f` must be safe to re-run: it should reload everything it changes.
+    async fn execute_with_retry<T, F, Fut>(
+        &self,
+        options: UowOptions,
+        policy: RetryPolicy,
+        f: F,
+    ) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(Box<dyn UnitOfWork>) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.execute_with_options(options.clone(), f.clone()).await {
+                Err(e) if e.is_retryable() && attempt < policy.max_attempts => {
+                    let delay = policy.backoff(attempt);
+                    tracing::warn!(error = ?e, attempt, ?delay, "Retrying transaction");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
 }
 impl<T: ?Sized + UowFactory> UowFactoryExt for T {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// How the transaction behind a unit of work is opened.
+///
+/// The default is a read-write transaction at the database's default
+/// isolation level without a statement timeout.
+#[derive(Debug, Clone, Default)]
+pub struct UowOptions {
+    pub isolation_level: Option<IsolationLevel>,
+    pub read_only: bool,
+    /// Upper bound for each statement run in the unit of work.
+    pub statement_timeout: Option<Duration>,
+    /// Recorded with the audit entries of the changes made.
+    pub audit: AuditContext,
+}
+
+impl UowOptions {
+    pub fn read_only() -> Self {
+        Self {
+            read_only: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.isolation_level = Some(isolation_level);
+        self
+    }
+
+    pub fn with_statement_timeout(mut self, statement_timeout: Duration) -> Self {
+        self.statement_timeout = Some(statement_timeout);
+        self
+    }
+
+    pub fn with_audit(mut self, audit: AuditContext) -> Self {
+        self.audit = audit;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff with full jitter, so racing requests spread out.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
+        ceiling.mul_f64(rand::random::<f64>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` units of work with `error`, then succeeds.
+    struct FlakyUowFactory {
+        failures: u32,
+        error: fn() -> DomainError,
+        attempts: AtomicU32,
+    }
+
+    impl FlakyUowFactory {
+        fn new(failures: u32, error: fn() -> DomainError) -> Self {
+            Self {
+                failures,
+                error,
+                attempts: AtomicU32::new(0),
+            }
+        }
+
+        fn attempts(&self) -> u32 {
+            self.attempts.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl UowFactory for FlakyUowFactory {
+        async fn execute_raw(&self, _: UowOptions, _: UowFnc) -> Result<Box<dyn Any + Send>> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                return Err((self.error)());
+            }
+            Ok(Box::new(()))
+        }
+    }
+
+    async fn run(fac: &FlakyUowFactory) -> Result<()> {
+        fac.execute_with_retry(UowOptions::default(), RetryPolicy::default(), async |_| {
+            Ok(())
+        })
+        .await
+    }
+
+    #[test]
+    fn only_conflicts_are_retryable() {
+        assert!(DomainError::ConcurrentModification.is_retryable());
+        assert!(DomainError::TransactionConflict("40001".into()).is_retryable());
+        assert!(!DomainError::RepositoryError("down".into()).is_retryable());
+        assert!(!DomainError::VersionMismatch { expected: 1 }.is_retryable());
+        assert!(!DomainError::Ticket(TicketError::NotFound).is_retryable());
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_the_maximum() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+        };
+        for _ in 0..100 {
+            assert!(policy.backoff(1) <= Duration::from_millis(10));
+            assert!(policy.backoff(2) <= Duration::from_millis(20));
+            assert!(policy.backoff(3) <= Duration::from_millis(40));
+            assert!(policy.backoff(4) <= Duration::from_millis(50));
+            assert!(policy.backoff(40) <= Duration::from_millis(50));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn conflicts_are_retried_until_the_unit_of_work_succeeds() {
+        let fac = FlakyUowFactory::new(2, || DomainError::ConcurrentModification);
+
+        run(&fac).await.unwrap();
+
+        assert_eq!(fac.attempts(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_last_conflict_is_returned_once_attempts_run_out() {
+        let fac = FlakyUowFactory::new(3, || DomainError::TransactionConflict("40001".into()));
+
+        let result = run(&fac).await;
+
+        assert!(matches!(result, Err(DomainError::TransactionConflict(_))));
+        assert_eq!(fac.attempts(), RetryPolicy::default().max_attempts);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn other_errors_are_not_retried() {
+        let fac = FlakyUowFactory::new(1, || DomainError::RepositoryError("down".into()));
+
+        let result = run(&fac).await;
+
+        assert!(matches!(result, Err(DomainError::RepositoryError(_))));
+        assert_eq!(fac.attempts(), 1);
+    }
+}
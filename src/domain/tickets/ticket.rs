@@ -1,8 +1,18 @@
+use crate::domain::actor::Actor;
 use crate::domain::tickets::ticket_description::{TicketDescription, TicketDescriptionError};
+use crate::domain::tickets::ticket_due_date::TicketDueDate;
 use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_event::{RecordedTicketEvent, TicketEvent};
 pub(crate) use crate::domain::tickets::ticket_id::TicketId;
-use crate::domain::tickets::ticket_status::TicketStatus;
+use crate::domain::tickets::ticket_label::{TicketLabel, TicketLabelError};
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::tickets::ticket_status::{TicketStatus, TicketStatusKind};
+use crate::domain::tickets::ticket_timestamps::TicketTimestamps;
 use crate::domain::tickets::ticket_title::{TicketTitle, TicketTitleError};
+use crate::domain::users::user::User;
+use crate::domain::users::user_id::UserId;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::BTreeSet;
 
 #[derive(Debug, Clone)]
 pub struct Ticket {
@@ -10,26 +20,151 @@ pub struct Ticket {
     title: TicketTitle,
     description: TicketDescription,
     status: TicketStatus,
-    assignee: Option<uuid::Uuid>,
+    assignee: Option<UserId>,
+    /// Subject of the actor who created the ticket; unknown for tickets
+    /// created before requests were authenticated.
+    reporter: Option<String>,
+    priority: TicketPriority,
+    due_date: Option<TicketDueDate>,
+    labels: BTreeSet<TicketLabel>,
     version: i64,
+    timestamps: TicketTimestamps,
+    /// Events recorded since the ticket was loaded, drained by the repository.
+    events: Vec<RecordedTicketEvent>,
 }
 
 impl Ticket {
+    pub const MAX_LABELS: usize = 10;
+
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn reconstruct(
         id: TicketId,
         title: impl TryInto<TicketTitle, Error = TicketTitleError>,
         description: impl TryInto<TicketDescription, Error = TicketDescriptionError>,
         status: TicketStatus,
-        assignee: Option<uuid::Uuid>,
+        assignee: Option<UserId>,
+        reporter: Option<String>,
+        priority: TicketPriority,
+        due_date: Option<NaiveDate>,
+        labels: impl IntoIterator<Item = String>,
         version: i64,
+        timestamps: TicketTimestamps,
     ) -> Self {
         Self {
             id,
             title: title.try_into().unwrap(),
             description: description.try_into().unwrap(),
             assignee,
+            reporter,
             status,
+            priority,
+            due_date: due_date.map(|date| date.try_into().unwrap()),
+            labels: labels
+                .into_iter()
+                .map(|label| label.try_into().unwrap())
+                .collect(),
             version,
+            timestamps,
+            events: Vec::new(),
+        }
+    }
+
+    /// Moves the ticket to the next version after a successful save, for
+    /// repositories that keep the aggregate itself instead of a row.
+    pub(crate) fn increment_version(&mut self) {
+        self.version += 1;
+    }
+
+    /// Rebuilds a ticket by folding its event stream, where each event moves
+    /// the ticket one version forward and the creation event is version 0.
+    ///
+    /// Returns `None` if the stream does not start with a creation event.
+    pub(crate) fn replay(
+        id: TicketId,
+        events: impl IntoIterator<Item = RecordedTicketEvent>,
+    ) -> Option<Self> {
+        let mut events = events.into_iter();
+        let RecordedTicketEvent {
+            event:
+                TicketEvent::Created {
+                    title,
+                    description,
+                    assignee,
+                    reporter,
+                    priority,
+                    due_date,
+                },
+            occurred_at,
+        } = events.next()?
+        else {
+            return None;
+        };
+        let status = match assignee {
+            Some(user_id) => TicketStatus::Assigned { user_id },
+            None => TicketStatus::default(),
+        };
+        let timestamps = TicketTimestamps::new(occurred_at);
+        let mut ticket = Self::reconstruct(
+            id,
+            title,
+            description,
+            status,
+            assignee,
+            reporter,
+            priority,
+            due_date,
+            [],
+            0,
+            timestamps,
+        );
+        for event in events {
+            ticket.apply(event);
+            ticket.version += 1;
+        }
+        Some(ticket)
+    }
+
+    /// Applies a recorded event without checking it again; it was valid when
+    /// it happened.
+    fn apply(&mut self, RecordedTicketEvent { event, occurred_at }: RecordedTicketEvent) {
+        self.timestamps.updated_at = occurred_at;
+        match event {
+            TicketEvent::Created { .. } => {}
+            TicketEvent::TitleChanged { title } => self.title = title.try_into().unwrap(),
+            TicketEvent::DescriptionChanged { description } => {
+                self.description = description.try_into().unwrap()
+            }
+            TicketEvent::PriorityChanged { priority } => self.priority = priority,
+            TicketEvent::DueDateChanged { due_date } => {
+                self.due_date = due_date.map(|date| date.try_into().unwrap())
+            }
+            TicketEvent::LabelAdded { label } => {
+                self.labels.insert(label.try_into().unwrap());
+            }
+            TicketEvent::LabelRemoved { label } => {
+                self.labels.remove(&label.try_into().unwrap());
+            }
+            TicketEvent::Assigned { user_id } => {
+                self.status = TicketStatus::Assigned { user_id };
+                self.assignee = Some(user_id);
+            }
+            TicketEvent::Unassigned => {
+                self.status = TicketStatus::Open;
+                self.assignee = None;
+            }
+            TicketEvent::Reopened => {
+                self.status = TicketStatus::Open;
+                self.assignee = None;
+                self.timestamps.closed_at = None;
+            }
+            TicketEvent::ProgressStarted { user_id } => {
+                self.status = TicketStatus::InProgress { user_id }
+            }
+            TicketEvent::Resolved => self.status = TicketStatus::Resolved,
+            TicketEvent::Closed => {
+                self.status = TicketStatus::Closed;
+                self.timestamps.closed_at = Some(occurred_at);
+            }
         }
     }
 }
@@ -38,28 +173,236 @@ impl Ticket {
     pub fn new(
         title: impl TryInto<TicketTitle, Error = TicketTitleError>,
         description: impl TryInto<TicketDescription, Error = TicketDescriptionError>,
-        assignee: Option<uuid::Uuid>,
+        assignee: Option<UserId>,
+        reporter: &Actor,
+        priority: TicketPriority,
+        due_date: Option<NaiveDate>,
+        now: DateTime<Utc>,
     ) -> Result<Self, TicketError> {
+        let status = match assignee {
+            Some(user_id) => TicketStatus::Assigned { user_id },
+            None => TicketStatus::default(),
+        };
+        let title = title.try_into()?;
+        let description = description.try_into()?;
+        let due_date = due_date
+            .map(|date| Self::future_due_date(date, now))
+            .transpose()?;
+        let created = TicketEvent::Created {
+            title: title.as_ref().to_string(),
+            description: description.as_ref().to_string(),
+            assignee,
+            reporter: Some(reporter.subject.clone()),
+            priority,
+            due_date: due_date.map(TicketDueDate::into_inner),
+        };
         Ok(Self {
             id: TicketId::new(),
-            title: title.try_into()?,
-            description: description.try_into()?,
-            status: Default::default(),
+            title,
+            description,
+            status,
             assignee,
+            reporter: Some(reporter.subject.clone()),
+            priority,
+            due_date,
+            labels: BTreeSet::new(),
             version: 0,
+            timestamps: TicketTimestamps::new(now),
+            events: vec![RecordedTicketEvent {
+                event: created,
+                occurred_at: now,
+            }],
         })
     }
 
-    pub fn assign(&mut self, user_id: uuid::Uuid) {
-        self.status = TicketStatus::Assigned { user_id };
+    /// Assigns the ticket, keeping `assignee` in sync with the status.
+    /// Deactivated users cannot be assigned.
+    pub fn assign(&mut self, user: &User, now: DateTime<Utc>) -> Result<(), TicketError> {
+        if !user.is_active() {
+            return Err(TicketError::InactiveAssignee);
+        }
+        let user_id = user.id();
+        self.transition(TicketStatus::Assigned { user_id })?;
+        self.assignee = Some(user_id);
+        self.record(TicketEvent::Assigned { user_id }, now);
+        Ok(())
     }
 
-    pub fn assignee(&self) -> Option<uuid::Uuid> {
+    pub fn assignee(&self) -> Option<UserId> {
         self.assignee
     }
 
-    pub fn close(&mut self) {
-        self.status = TicketStatus::Closed;
+    pub fn reporter(&self) -> Option<&str> {
+        self.reporter.as_deref()
+    }
+
+    pub fn unassign(&mut self, now: DateTime<Utc>) -> Result<(), TicketError> {
+        if self.status.user_id().is_none() {
+            return Err(TicketError::InvalidTransition {
+                from: self.status.kind(),
+                to: TicketStatusKind::Open,
+            });
+        }
+        self.transition(TicketStatus::Open)?;
+        self.assignee = None;
+        self.record(TicketEvent::Unassigned, now);
+        Ok(())
+    }
+
+    pub fn start_progress(&mut self, now: DateTime<Utc>) -> Result<(), TicketError> {
+        let user_id = self
+            .status
+            .user_id()
+            .ok_or(TicketError::InvalidTransition {
+                from: self.status.kind(),
+                to: TicketStatusKind::InProgress,
+            })?;
+        self.transition(TicketStatus::InProgress { user_id })?;
+        self.record(TicketEvent::ProgressStarted { user_id }, now);
+        Ok(())
+    }
+
+    pub fn resolve(&mut self, now: DateTime<Utc>) -> Result<(), TicketError> {
+        self.transition(TicketStatus::Resolved)?;
+        self.record(TicketEvent::Resolved, now);
+        Ok(())
+    }
+
+    pub fn close(&mut self, now: DateTime<Utc>) -> Result<(), TicketError> {
+        self.transition(TicketStatus::Closed)?;
+        self.timestamps.closed_at = Some(now);
+        self.record(TicketEvent::Closed, now);
+        Ok(())
+    }
+
+    /// Reopens a resolved or closed ticket as unassigned.
+    pub fn reopen(&mut self, now: DateTime<Utc>) -> Result<(), TicketError> {
+        if !matches!(self.status, TicketStatus::Resolved | TicketStatus::Closed) {
+            return Err(TicketError::InvalidTransition {
+                from: self.status.kind(),
+                to: TicketStatusKind::Open,
+            });
+        }
+        self.transition(TicketStatus::Open)?;
+        self.assignee = None;
+        self.timestamps.closed_at = None;
+        self.record(TicketEvent::Reopened, now);
+        Ok(())
+    }
+
+    fn transition(&mut self, to: TicketStatus) -> Result<(), TicketError> {
+        let from = self.status.kind();
+        if !from.can_transition_to(to.kind()) {
+            return Err(TicketError::InvalidTransition {
+                from,
+                to: to.kind(),
+            });
+        }
+        self.status = to;
+        Ok(())
+    }
+
+    /// Keeps an event for publishing and marks the ticket as changed.
+    fn record(&mut self, event: TicketEvent, now: DateTime<Utc>) {
+        self.timestamps.updated_at = now;
+        self.events.push(RecordedTicketEvent {
+            event,
+            occurred_at: now,
+        });
+    }
+
+    pub fn change_title(
+        &mut self,
+        title: impl TryInto<TicketTitle, Error = TicketTitleError>,
+        now: DateTime<Utc>,
+    ) -> Result<(), TicketError> {
+        self.title = title.try_into()?;
+        let title = self.title();
+        self.record(TicketEvent::TitleChanged { title }, now);
+        Ok(())
+    }
+
+    pub fn change_description(
+        &mut self,
+        description: impl TryInto<TicketDescription, Error = TicketDescriptionError>,
+        now: DateTime<Utc>,
+    ) -> Result<(), TicketError> {
+        self.description = description.try_into()?;
+        let description = self.description();
+        self.record(TicketEvent::DescriptionChanged { description }, now);
+        Ok(())
+    }
+
+    pub fn change_priority(&mut self, priority: TicketPriority, now: DateTime<Utc>) {
+        self.priority = priority;
+        self.record(TicketEvent::PriorityChanged { priority }, now);
+    }
+
+    /// Sets or, with `None`, clears the due date. A new due date cannot lie
+    /// in the past.
+    pub fn change_due_date(
+        &mut self,
+        due_date: Option<NaiveDate>,
+        now: DateTime<Utc>,
+    ) -> Result<(), TicketError> {
+        self.due_date = due_date
+            .map(|date| Self::future_due_date(date, now))
+            .transpose()?;
+        let due_date = self.due_date();
+        self.record(TicketEvent::DueDateChanged { due_date }, now);
+        Ok(())
+    }
+
+    fn future_due_date(date: NaiveDate, now: DateTime<Utc>) -> Result<TicketDueDate, TicketError> {
+        if date < now.date_naive() {
+            return Err(TicketError::DueDateInPast);
+        }
+        Ok(date.try_into()?)
+    }
+
+    /// Adds a label, returning whether the ticket did not have it yet.
+    pub fn add_label(
+        &mut self,
+        label: impl TryInto<TicketLabel, Error = TicketLabelError>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, TicketError> {
+        let label = label.try_into()?;
+        if self.labels.contains(&label) {
+            return Ok(false);
+        }
+        if self.labels.len() >= Self::MAX_LABELS {
+            return Err(TicketError::TooManyLabels {
+                max: Self::MAX_LABELS,
+            });
+        }
+        let event = TicketEvent::LabelAdded {
+            label: label.as_ref().to_string(),
+        };
+        self.labels.insert(label);
+        self.record(event, now);
+        Ok(true)
+    }
+
+    /// Removes a label, returning whether the ticket had it.
+    pub fn remove_label(
+        &mut self,
+        label: impl TryInto<TicketLabel, Error = TicketLabelError>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, TicketError> {
+        let label = label.try_into()?;
+        if !self.labels.remove(&label) {
+            return Ok(false);
+        }
+        let label = label.as_ref().to_string();
+        self.record(TicketEvent::LabelRemoved { label }, now);
+        Ok(true)
+    }
+
+    /// Whether the due date has passed on `today` while the ticket is still
+    /// unresolved.
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        let unresolved = !matches!(self.status, TicketStatus::Resolved | TicketStatus::Closed);
+        unresolved && self.due_date().is_some_and(|due| due < today)
     }
 
     pub fn id(&self) -> TicketId {
@@ -77,7 +420,145 @@ impl Ticket {
     pub fn description(&self) -> String {
         self.description.as_ref().to_string()
     }
+
+    pub fn priority(&self) -> TicketPriority {
+        self.priority
+    }
+
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        self.due_date.map(TicketDueDate::into_inner)
+    }
+
+    /// Labels in alphabetical order.
+    pub fn labels(&self) -> Vec<String> {
+        self.labels.iter().map(|l| l.as_ref().to_string()).collect()
+    }
+
+    pub fn has_label(&self, label: &TicketLabel) -> bool {
+        self.labels.contains(label)
+    }
+
     pub fn version(&self) -> i64 {
         self.version
     }
+
+    pub fn timestamps(&self) -> TicketTimestamps {
+        self.timestamps
+    }
+
+    /// Hands over the events recorded so far, so they are published once.
+    pub fn take_events(&mut self) -> Vec<RecordedTicketEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap()
+    }
+
+    fn ticket() -> Ticket {
+        let reporter = Actor::new("tester", vec![]);
+        Ticket::new(
+            "Printer is jammed",
+            "Paper stuck in tray 2",
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            now(),
+        )
+        .unwrap()
+    }
+
+    fn assert_invalid(
+        result: Result<(), TicketError>,
+        from: TicketStatusKind,
+        to: TicketStatusKind,
+    ) {
+        assert!(
+            matches!(result, Err(TicketError::InvalidTransition { from: f, to: t }) if f == from && t == to),
+            "expected {from} -> {to} to be rejected, got {result:?}"
+        );
+    }
+
+    #[test]
+    fn a_ticket_goes_through_its_lifecycle_and_can_be_reopened() {
+        let user = User::new("Alice", now()).unwrap();
+        let mut ticket = ticket();
+
+        ticket.assign(&user, now()).unwrap();
+        ticket.start_progress(now()).unwrap();
+        ticket.resolve(now()).unwrap();
+        ticket.close(now()).unwrap();
+        assert_eq!(ticket.status(), TicketStatus::Closed);
+        assert!(ticket.timestamps().closed_at.is_some());
+
+        ticket.reopen(now()).unwrap();
+        assert_eq!(ticket.status(), TicketStatus::Open);
+        assert_eq!(ticket.assignee(), None);
+        assert_eq!(ticket.timestamps().closed_at, None);
+    }
+
+    #[test]
+    fn illegal_transitions_are_rejected_and_leave_the_ticket_unchanged() {
+        use TicketStatusKind::*;
+        let mut ticket = ticket();
+        let events = ticket.events.len();
+
+        assert_invalid(ticket.reopen(now()), Open, Open);
+        assert_invalid(ticket.unassign(now()), Open, Open);
+        assert_invalid(ticket.start_progress(now()), Open, InProgress);
+        assert_invalid(ticket.resolve(now()), Open, Resolved);
+        assert_eq!(ticket.status(), TicketStatus::Open);
+        assert_eq!(ticket.events.len(), events);
+
+        ticket.close(now()).unwrap();
+        assert_invalid(ticket.close(now()), Closed, Closed);
+        assert_invalid(ticket.resolve(now()), Closed, Resolved);
+        assert_eq!(ticket.status(), TicketStatus::Closed);
+    }
+
+    #[test]
+    fn deactivated_users_cannot_be_assigned() {
+        let mut user = User::new("Alice", now()).unwrap();
+        user.deactivate();
+        let mut ticket = ticket();
+
+        assert!(matches!(
+            ticket.assign(&user, now()),
+            Err(TicketError::InactiveAssignee)
+        ));
+        assert_eq!(ticket.assignee(), None);
+    }
+
+    #[test]
+    fn labels_are_added_once_and_limited_per_ticket() {
+        let mut ticket = ticket();
+
+        assert!(ticket.add_label("Hardware", now()).unwrap());
+        assert!(!ticket.add_label("hardware ", now()).unwrap());
+        for n in 1..Ticket::MAX_LABELS {
+            ticket.add_label(format!("label-{n}"), now()).unwrap();
+        }
+        assert!(matches!(
+            ticket.add_label("one-too-many", now()),
+            Err(TicketError::TooManyLabels {
+                max: Ticket::MAX_LABELS
+            })
+        ));
+        assert!(matches!(
+            ticket.add_label("no spaces", now()),
+            Err(TicketError::TicketLabelError(
+                TicketLabelError::PredicateViolated
+            ))
+        ));
+        assert!(ticket.remove_label("HARDWARE", now()).unwrap());
+        assert!(!ticket.remove_label("hardware", now()).unwrap());
+        assert_eq!(ticket.labels().len(), Ticket::MAX_LABELS - 1);
+    }
 }
@@ -0,0 +1,67 @@
+use crate::domain::actor::Actor;
+use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_id::TicketId;
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Who is making the changes of a unit of work, stored with its audit entries.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor: Option<String>,
+    /// Trace id of the request, to find the matching logs and traces.
+    pub trace_id: Option<String>,
+}
+
+impl AuditContext {
+    pub fn with_actor(mut self, actor: &Actor) -> Self {
+        self.actor = Some(actor.subject.clone());
+        self
+    }
+}
+
+/// The audited values of a ticket at one version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketSnapshot {
+    pub title: String,
+    pub description: String,
+    /// Status name as returned by `TicketStatusKind::as_str`.
+    pub status: String,
+    pub assignee: Option<Uuid>,
+    // Older entries were written without these
+    #[serde(default)]
+    pub priority: TicketPriority,
+    #[serde(default)]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub version: i64,
+}
+
+impl From<&Ticket> for TicketSnapshot {
+    fn from(ticket: &Ticket) -> Self {
+        Self {
+            title: ticket.title(),
+            description: ticket.description(),
+            status: ticket.status().kind().as_str().to_owned(),
+            assignee: ticket.assignee().map(|a| a.value()),
+            priority: ticket.priority(),
+            due_date: ticket.due_date(),
+            labels: ticket.labels(),
+            version: ticket.version(),
+        }
+    }
+}
+
+/// One change written through a ticket repository.
+#[derive(Debug, Clone)]
+pub struct TicketAuditEntry {
+    pub ticket_id: TicketId,
+    /// `None` for the entry recording the creation of the ticket.
+    pub previous: Option<TicketSnapshot>,
+    pub current: TicketSnapshot,
+    pub actor: Option<String>,
+    pub trace_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
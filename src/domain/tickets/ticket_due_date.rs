@@ -0,0 +1,11 @@
+use chrono::{Datelike, NaiveDate};
+use nutype::nutype;
+
+/// Day by which a ticket should be resolved.
+///
+/// Limited to a plausible range so typos such as year 20026 are rejected.
+#[nutype(
+    validate(predicate = |date| (2000..=2999).contains(&date.year())),
+    derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, TryFrom)
+)]
+pub struct TicketDueDate(NaiveDate);
@@ -1,4 +1,7 @@
 use crate::domain::tickets::ticket_description::TicketDescriptionError;
+use crate::domain::tickets::ticket_due_date::TicketDueDateError;
+use crate::domain::tickets::ticket_label::TicketLabelError;
+use crate::domain::tickets::ticket_status::TicketStatusKind;
 use crate::domain::tickets::ticket_title::TicketTitleError;
 use thiserror::Error;
 
@@ -14,10 +17,33 @@ pub enum TicketError {
     TooLongDescription,
     #[error("Ticket not found")]
     NotFound,
+    #[error("Search text cannot be empty")]
+    EmptySearch,
+    #[error("Cursor must be a next_cursor returned by a previous page")]
+    InvalidCursor,
     #[error("Invalid usecase status")]
     InvalidStatus,
+    #[error("Ticket priority must be one of low, medium, high or critical")]
+    InvalidPriority,
+    #[error("Ticket due date cannot be in the past")]
+    DueDateInPast,
+    #[error("Ticket cannot have more than {max} labels")]
+    TooManyLabels { max: usize },
+    #[error("Tickets can only be assigned to existing users")]
+    UnknownAssignee,
+    #[error("Tickets cannot be assigned to deactivated users")]
+    InactiveAssignee,
+    #[error("Cannot change ticket status from {from} to {to}")]
+    InvalidTransition {
+        from: TicketStatusKind,
+        to: TicketStatusKind,
+    },
     #[error("Ticket description error: {0}")]
     TicketDescriptionError(#[from] TicketDescriptionError),
     #[error("Ticket title error: {0}")]
     TicketTitleError(#[from] TicketTitleError),
+    #[error("Ticket due date error: {0}")]
+    TicketDueDateError(#[from] TicketDueDateError),
+    #[error("Ticket label error: {0}")]
+    TicketLabelError(#[from] TicketLabelError),
 }
@@ -0,0 +1,77 @@
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::users::user_id::UserId;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Something that happened to a ticket, recorded by the aggregate and
+/// published to other services once the change is committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TicketEvent {
+    #[serde(rename = "TicketCreated")]
+    Created {
+        title: String,
+        description: String,
+        assignee: Option<UserId>,
+        // Absent from events recorded before tickets had a priority and due date
+        #[serde(default)]
+        priority: TicketPriority,
+        #[serde(default)]
+        due_date: Option<NaiveDate>,
+        // Absent from events recorded before requests were authenticated
+        #[serde(default)]
+        reporter: Option<String>,
+    },
+    #[serde(rename = "TicketTitleChanged")]
+    TitleChanged { title: String },
+    #[serde(rename = "TicketDescriptionChanged")]
+    DescriptionChanged { description: String },
+    #[serde(rename = "TicketPriorityChanged")]
+    PriorityChanged { priority: TicketPriority },
+    #[serde(rename = "TicketDueDateChanged")]
+    DueDateChanged { due_date: Option<NaiveDate> },
+    #[serde(rename = "TicketLabelAdded")]
+    LabelAdded { label: String },
+    #[serde(rename = "TicketLabelRemoved")]
+    LabelRemoved { label: String },
+    #[serde(rename = "TicketAssigned")]
+    Assigned { user_id: UserId },
+    #[serde(rename = "TicketUnassigned")]
+    Unassigned,
+    #[serde(rename = "TicketProgressStarted")]
+    ProgressStarted { user_id: UserId },
+    #[serde(rename = "TicketResolved")]
+    Resolved,
+    #[serde(rename = "TicketClosed")]
+    Closed,
+    #[serde(rename = "TicketReopened")]
+    Reopened,
+}
+
+impl TicketEvent {
+    /// The name consumers route on; matches the `type` field of the payload.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            TicketEvent::Created { .. } => "TicketCreated",
+            TicketEvent::TitleChanged { .. } => "TicketTitleChanged",
+            TicketEvent::DescriptionChanged { .. } => "TicketDescriptionChanged",
+            TicketEvent::PriorityChanged { .. } => "TicketPriorityChanged",
+            TicketEvent::DueDateChanged { .. } => "TicketDueDateChanged",
+            TicketEvent::LabelAdded { .. } => "TicketLabelAdded",
+            TicketEvent::LabelRemoved { .. } => "TicketLabelRemoved",
+            TicketEvent::Assigned { .. } => "TicketAssigned",
+            TicketEvent::Unassigned => "TicketUnassigned",
+            TicketEvent::ProgressStarted { .. } => "TicketProgressStarted",
+            TicketEvent::Resolved => "TicketResolved",
+            TicketEvent::Closed => "TicketClosed",
+            TicketEvent::Reopened => "TicketReopened",
+        }
+    }
+}
+
+/// An event together with the time the ticket recorded it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedTicketEvent {
+    pub event: TicketEvent,
+    pub occurred_at: DateTime<Utc>,
+}
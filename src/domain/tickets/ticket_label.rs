@@ -0,0 +1,53 @@
+use nutype::nutype;
+
+/// A tag on a ticket, compared case-insensitively.
+///
+/// Labels are used in URL paths and comma-separated filters, so they are
+/// limited to letters, digits, `-`, `_` and `:`.
+#[nutype(
+    sanitize(trim, lowercase),
+    validate(
+        not_empty,
+        len_char_max = 30,
+        predicate = |label| label
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':'))
+    ),
+    derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, AsRef, TryFrom)
+)]
+pub struct TicketLabel(String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_are_trimmed_lowercased_and_limited_to_safe_characters() {
+        assert_eq!(
+            TicketLabel::try_from(" Team:Printing ").unwrap().as_ref(),
+            "team:printing"
+        );
+        assert_eq!(
+            TicketLabel::try_from("x".repeat(30))
+                .unwrap()
+                .as_ref()
+                .len(),
+            30
+        );
+        assert_eq!(
+            TicketLabel::try_from("   "),
+            Err(TicketLabelError::NotEmptyViolated)
+        );
+        assert_eq!(
+            TicketLabel::try_from("x".repeat(31)),
+            Err(TicketLabelError::LenCharMaxViolated)
+        );
+        for invalid in ["two words", "a,b", "a/b", "50%"] {
+            assert_eq!(
+                TicketLabel::try_from(invalid),
+                Err(TicketLabelError::PredicateViolated),
+                "{invalid}"
+            );
+        }
+    }
+}
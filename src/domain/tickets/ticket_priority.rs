@@ -0,0 +1,47 @@
+use crate::domain::tickets::ticket_error::TicketError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+impl TicketPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketPriority::Low => "low",
+            TicketPriority::Medium => "medium",
+            TicketPriority::High => "high",
+            TicketPriority::Critical => "critical",
+        }
+    }
+}
+
+impl Display for TicketPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TicketPriority {
+    type Err = TicketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(TicketPriority::Low),
+            "medium" => Ok(TicketPriority::Medium),
+            "high" => Ok(TicketPriority::High),
+            "critical" => Ok(TicketPriority::Critical),
+            _ => Err(TicketError::InvalidPriority),
+        }
+    }
+}
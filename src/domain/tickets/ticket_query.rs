@@ -0,0 +1,277 @@
+use crate::domain::error::DomainError;
+use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_label::TicketLabel;
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::tickets::ticket_status::TicketStatusKind;
+use crate::domain::users::user_id::UserId;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Filter and page window for listing tickets.
+///
+/// Tickets are ordered by creation time, ties broken by id, and `cursor` is
+/// the position of the last ticket of the previous page, so pages stay stable
+/// while tickets are being inserted.
+#[derive(Debug, Clone, Default)]
+pub struct TicketQuery {
+    pub status: Option<TicketStatusKind>,
+    pub assignee: Option<UserId>,
+    pub title: Option<String>,
+    pub priority: Option<TicketPriority>,
+    pub overdue: Option<bool>,
+    /// Tickets with at least one of these labels; ignored when empty.
+    pub labels_any: Vec<TicketLabel>,
+    /// Tickets with every one of these labels; ignored when empty.
+    pub labels_all: Vec<TicketLabel>,
+    /// The day `overdue` is judged against.
+    pub as_of: NaiveDate,
+    pub cursor: Option<TicketCursor>,
+    pub limit: i64,
+}
+
+impl TicketQuery {
+    pub const DEFAULT_LIMIT: i64 = 20;
+    pub const MAX_LIMIT: i64 = 100;
+
+    /// Whether `ticket` passes the filter, ignoring the page window.
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        self.status.is_none_or(|s| ticket.status().kind() == s)
+            && self.assignee.is_none_or(|a| ticket.assignee() == Some(a))
+            && self.title.as_ref().is_none_or(|title| {
+                ticket
+                    .title()
+                    .to_lowercase()
+                    .contains(&title.to_lowercase())
+            })
+            && self.priority.is_none_or(|p| ticket.priority() == p)
+            && self
+                .overdue
+                .is_none_or(|o| ticket.is_overdue(self.as_of) == o)
+            && (self.labels_any.is_empty() || self.labels_any.iter().any(|l| ticket.has_label(l)))
+            && self.labels_all.iter().all(|l| ticket.has_label(l))
+    }
+
+    /// Cuts the page window out of all tickets matching the filter, for
+    /// stores that cannot filter before loading.
+    pub fn paginate(&self, mut matches: Vec<Ticket>) -> TicketPage {
+        matches.sort_by_key(TicketCursor::after);
+        let total = matches.len() as i64;
+
+        let items = matches
+            .into_iter()
+            .filter(|t| self.cursor.is_none_or(|c| TicketCursor::after(t) > c))
+            .take(self.limit as usize + 1)
+            .collect::<Vec<_>>();
+        self.page(items, total)
+    }
+
+    /// Builds the page from up to `limit + 1` tickets following the cursor,
+    /// the extra one telling whether there is a next page.
+    pub fn page(&self, mut items: Vec<Ticket>, total: i64) -> TicketPage {
+        let next_cursor = if items.len() as i64 > self.limit {
+            items.truncate(self.limit as usize);
+            items.last().map(TicketCursor::after)
+        } else {
+            None
+        };
+
+        TicketPage {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+}
+
+/// Position in the list order, just after a ticket.
+///
+/// It is the creation time and id of the ticket, shown to clients only as
+/// URL-safe base64 so they pass it back instead of building their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TicketCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl TicketCursor {
+    pub fn after(ticket: &Ticket) -> Self {
+        Self {
+            created_at: ticket.timestamps().created_at,
+            id: ticket.id().value(),
+        }
+    }
+}
+
+impl Display for TicketCursor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let created_at = self.created_at.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        let encoded = URL_SAFE_NO_PAD.encode(format!("{created_at}_{}", self.id));
+        f.write_str(&encoded)
+    }
+}
+
+impl FromStr for TicketCursor {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let decoded = URL_SAFE_NO_PAD
+            .decode(value)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or(TicketError::InvalidCursor)?;
+        let (created_at, id) = decoded.split_once('_').ok_or(TicketError::InvalidCursor)?;
+        Ok(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|_| TicketError::InvalidCursor)?
+                .to_utc(),
+            id: Uuid::parse_str(id).map_err(|_| TicketError::InvalidCursor)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TicketPage {
+    pub items: Vec<Ticket>,
+    pub next_cursor: Option<TicketCursor>,
+    /// Number of tickets matching the filter, regardless of the page window.
+    pub total: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use chrono::{TimeZone, Utc};
+
+    fn ticket(labels: &[&str]) -> Ticket {
+        created_at(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap(), labels)
+    }
+
+    fn created_at(now: DateTime<Utc>, labels: &[&str]) -> Ticket {
+        let reporter = Actor::new("tester", vec![]);
+        let mut ticket = Ticket::new(
+            "Printer is jammed",
+            "Paper stuck in tray 2",
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            now,
+        )
+        .unwrap();
+        for label in labels {
+            ticket.add_label(*label, now).unwrap();
+        }
+        ticket
+    }
+
+    fn labels(names: &[&str]) -> Vec<TicketLabel> {
+        names
+            .iter()
+            .map(|name| TicketLabel::try_from(*name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn labels_any_matches_one_label_and_labels_all_every_label() {
+        let tickets = [
+            ticket(&[]),
+            ticket(&["hardware"]),
+            ticket(&["network"]),
+            ticket(&["hardware", "urgent"]),
+        ];
+        let matching = |query: TicketQuery| {
+            tickets
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| query.matches(t))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching(TicketQuery::default()), [0, 1, 2, 3]);
+        assert_eq!(
+            matching(TicketQuery {
+                labels_any: labels(&["hardware", "network"]),
+                ..TicketQuery::default()
+            }),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            matching(TicketQuery {
+                labels_all: labels(&["hardware", "urgent"]),
+                ..TicketQuery::default()
+            }),
+            [3]
+        );
+        assert_eq!(
+            matching(TicketQuery {
+                labels_any: labels(&["network", "urgent"]),
+                labels_all: labels(&["hardware"]),
+                ..TicketQuery::default()
+            }),
+            [3]
+        );
+        assert!(matching(TicketQuery {
+            labels_any: labels(&["software"]),
+            ..TicketQuery::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn pages_follow_creation_order_and_resume_after_the_cursor() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        // Two tickets per minute, handed over in reverse id order
+        let mut tickets = (0..5)
+            .map(|minute| created_at(start + chrono::Duration::minutes(minute / 2), &[]))
+            .collect::<Vec<_>>();
+        tickets.sort_by_key(|t| std::cmp::Reverse(t.id().value()));
+        let expected = {
+            let mut sorted = tickets.clone();
+            sorted.sort_by_key(|t| (t.timestamps().created_at, t.id().value()));
+            sorted.iter().map(|t| t.id()).collect::<Vec<_>>()
+        };
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let query = TicketQuery {
+                cursor,
+                limit: 2,
+                ..TicketQuery::default()
+            };
+            let page = query.paginate(tickets.clone());
+            assert_eq!(page.total, 5);
+            seen.extend(page.items.iter().map(|t| t.id()));
+            let Some(next) = page.next_cursor else {
+                break;
+            };
+            cursor = Some(next.to_string().parse().unwrap());
+            assert_eq!(cursor, Some(next));
+        }
+
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn cursors_are_encoded_and_only_decoded_from_their_encoding() {
+        let cursor = TicketCursor::after(&ticket(&[]));
+        let encoded = cursor.to_string();
+        let readable = format!("{}_{}", cursor.created_at.to_rfc3339(), cursor.id);
+
+        assert!(!encoded.contains(&cursor.id.to_string()));
+        assert_eq!(encoded.parse::<TicketCursor>().unwrap(), cursor);
+        for invalid in [readable.as_str(), "not-a-cursor", "bm90LWEtY3Vyc29y", ""] {
+            assert!(matches!(
+                invalid.parse::<TicketCursor>(),
+                Err(DomainError::Ticket(TicketError::InvalidCursor))
+            ));
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_error::TicketError;
+
+/// Keyword search over ticket titles and descriptions.
+///
+/// `text` is split on whitespace, and a ticket matches when it contains every
+/// term. Stores with a full-text index may match more loosely, e.g. on word
+/// stems.
+#[derive(Debug, Clone)]
+pub struct TicketSearch {
+    pub text: String,
+    pub limit: i64,
+}
+
+/// A ticket found by a search, with its matching terms wrapped in
+/// `HIGHLIGHT_START` and `HIGHLIGHT_END`.
+///
+/// `title` and `snippet` are HTML: the ticket text in them is escaped, so
+/// only the highlight markup is interpreted.
+#[derive(Debug, Clone)]
+pub struct TicketSearchHit {
+    pub ticket: Ticket,
+    /// Higher is more relevant. Only comparable within one search.
+    pub rank: f32,
+    pub title: String,
+    /// The part of the description around the first match.
+    pub snippet: String,
+}
+
+impl TicketSearch {
+    pub const DEFAULT_LIMIT: i64 = 20;
+    pub const MAX_LIMIT: i64 = 100;
+    pub const HIGHLIGHT_START: &str = "<mark>";
+    pub const HIGHLIGHT_END: &str = "</mark>";
+    /// Characters of description shown around the first match.
+    pub const SNIPPET_CHARS: usize = 80;
+    /// Weight of a description match relative to a title match.
+    const DESCRIPTION_WEIGHT: f32 = 0.4;
+
+    pub fn new(text: impl Into<String>, limit: i64) -> Result<Self, TicketError> {
+        let text = text.into().trim().to_owned();
+        if text.is_empty() {
+            return Err(TicketError::EmptySearch);
+        }
+        Ok(Self { text, limit })
+    }
+
+    pub fn terms(&self) -> Vec<&str> {
+        self.text.split_whitespace().collect()
+    }
+
+    /// Matches and ranks `ticket` by plain substring search, for stores
+    /// without a full-text index.
+    pub fn hit(&self, ticket: &Ticket) -> Option<TicketSearchHit> {
+        let title = ticket.title();
+        let description = ticket.description();
+        let terms = self.terms();
+        let mut score = 0.0;
+        for term in &terms {
+            let in_title = find_ignoring_case(&title, term, 0).is_some();
+            let in_description = find_ignoring_case(&description, term, 0).is_some();
+            if !in_title && !in_description {
+                return None;
+            }
+            score += if in_title { 1.0 } else { 0.0 };
+            score += if in_description {
+                Self::DESCRIPTION_WEIGHT
+            } else {
+                0.0
+            };
+        }
+        Some(TicketSearchHit {
+            rank: score / terms.len() as f32,
+            title: self.highlight(&title),
+            snippet: self.snippet(&description),
+            ticket: ticket.clone(),
+        })
+    }
+
+    /// Orders hits from most to least relevant and keeps the first `limit`.
+    pub fn rank(&self, mut hits: Vec<TicketSearchHit>) -> Vec<TicketSearchHit> {
+        hits.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then_with(|| a.ticket.id().value().cmp(&b.ticket.id().value()))
+        });
+        hits.truncate(self.limit as usize);
+        hits
+    }
+
+    /// Wraps every occurrence of a term in `text`, ignoring case, and
+    /// escapes the rest for HTML.
+    pub fn highlight(&self, text: &str) -> String {
+        let mut matches = self
+            .terms()
+            .into_iter()
+            .flat_map(|term| {
+                let mut found = Vec::new();
+                let mut from = 0;
+                while let Some((start, end)) = find_ignoring_case(text, term, from) {
+                    found.push((start, end));
+                    from = end;
+                }
+                found
+            })
+            .collect::<Vec<_>>();
+        matches.sort();
+
+        let mut highlighted = String::with_capacity(text.len());
+        let mut position = 0;
+        for (start, end) in matches {
+            // Skip matches overlapping one already highlighted
+            if start < position {
+                continue;
+            }
+            push_escaped(&mut highlighted, &text[position..start]);
+            highlighted.push_str(Self::HIGHLIGHT_START);
+            push_escaped(&mut highlighted, &text[start..end]);
+            highlighted.push_str(Self::HIGHLIGHT_END);
+            position = end;
+        }
+        push_escaped(&mut highlighted, &text[position..]);
+        highlighted
+    }
+
+    /// Cuts `SNIPPET_CHARS` characters around the first match out of `text`
+    /// and highlights them.
+    pub fn snippet(&self, text: &str) -> String {
+        let first = self
+            .terms()
+            .into_iter()
+            .filter_map(|term| find_ignoring_case(text, term, 0))
+            .map(|(start, _)| text[..start].chars().count())
+            .min()
+            .unwrap_or(0);
+        let total = text.chars().count();
+        let start = first
+            .saturating_sub(Self::SNIPPET_CHARS / 4)
+            .min(total.saturating_sub(Self::SNIPPET_CHARS));
+        let window = text
+            .chars()
+            .skip(start)
+            .take(Self::SNIPPET_CHARS)
+            .collect::<String>();
+        let prefix = if start > 0 { "…" } else { "" };
+        let suffix = if start + Self::SNIPPET_CHARS < total {
+            "…"
+        } else {
+            ""
+        };
+        format!("{prefix}{}{suffix}", self.highlight(&window))
+    }
+}
+
+/// Appends `text` escaped for HTML, matching `html_escape` in the database.
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Byte range of the first occurrence of `needle` in `haystack` at or after
+/// byte `from`, comparing characters by their lowercase forms.
+fn find_ignoring_case(haystack: &str, needle: &str, from: usize) -> Option<(usize, usize)> {
+    if needle.is_empty() {
+        return None;
+    }
+    haystack[from..].char_indices().find_map(|(offset, _)| {
+        let start = from + offset;
+        let mut candidate = haystack[start..].char_indices();
+        let matched = needle.chars().all(|n| {
+            candidate
+                .next()
+                .is_some_and(|(_, c)| c.to_lowercase().eq(n.to_lowercase()))
+        });
+        matched.then(|| {
+            let end = candidate.next().map_or(haystack.len(), |(i, _)| start + i);
+            (start, end)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+    use chrono::{TimeZone, Utc};
+
+    fn ticket(title: &str, description: &str) -> Ticket {
+        let reporter = Actor::new("tester", vec![]);
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        Ticket::new(
+            title,
+            description,
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            now,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn highlights_every_term_ignoring_case_and_cuts_snippets_around_the_first_match() {
+        let search = TicketSearch::new("printer 紙詰まり", 20).unwrap();
+        let description = format!("{}Printer の紙詰まり", "あ".repeat(100));
+
+        assert_eq!(
+            search.highlight("Printer が紙詰まり"),
+            "<mark>Printer</mark> が<mark>紙詰まり</mark>"
+        );
+        assert_eq!(
+            search.snippet(&description),
+            format!(
+                "…{}<mark>Printer</mark> の<mark>紙詰まり</mark>",
+                "あ".repeat(67)
+            )
+        );
+        assert!(TicketSearch::new("  ", 20).is_err());
+    }
+
+    #[test]
+    fn ticket_text_is_escaped_around_the_highlights() {
+        let search = TicketSearch::new("script", 20).unwrap();
+
+        assert_eq!(
+            search.highlight("<script>alert('x & y')</script>"),
+            "&lt;<mark>script</mark>&gt;alert(&#39;x &amp; y&#39;)&lt;/<mark>script</mark>&gt;"
+        );
+        assert_eq!(
+            search.snippet("Printer says \"<b>script</b>\""),
+            "Printer says &quot;&lt;b&gt;<mark>script</mark>&lt;/b&gt;&quot;"
+        );
+    }
+
+    #[test]
+    fn title_matches_rank_above_description_matches_and_every_term_must_match() {
+        let search = TicketSearch::new("printer jammed", 2).unwrap();
+        let in_title = ticket("Printer jammed", "Paper stuck in tray 2");
+        let in_both = ticket("Printer jammed", "The printer is jammed again");
+        let in_description = ticket("Office supplies", "The printer is jammed");
+        let partial = ticket("Printer out of toner", "Replace the cartridge");
+
+        let hits = [&in_title, &in_both, &in_description, &partial]
+            .into_iter()
+            .filter_map(|t| search.hit(t))
+            .collect::<Vec<_>>();
+        assert_eq!(hits.len(), 3);
+        let ranked = search.rank(hits);
+
+        let ids = ranked.iter().map(|h| h.ticket.id()).collect::<Vec<_>>();
+        assert_eq!(ids, [in_both.id(), in_title.id()]);
+        assert!(ranked[0].rank > ranked[1].rank);
+        assert_eq!(ranked[1].title, "<mark>Printer</mark> <mark>jammed</mark>");
+    }
+}
@@ -1,4 +1,5 @@
 use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::users::user_id::UserId;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -6,7 +7,9 @@ use std::str::FromStr;
 pub enum TicketStatus {
     #[default]
     Open,
-    Assigned { user_id: uuid::Uuid },
+    Assigned { user_id: UserId },
+    InProgress { user_id: UserId },
+    Resolved,
     Closed,
 }
 
@@ -16,15 +19,107 @@ impl Display for TicketStatus {
     }
 }
 
-impl FromStr for TicketStatus {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TicketStatusKind {
+    Open,
+    Assigned,
+    InProgress,
+    Resolved,
+    Closed,
+}
+
+impl TicketStatus {
+    pub fn kind(&self) -> TicketStatusKind {
+        match self {
+            TicketStatus::Open => TicketStatusKind::Open,
+            TicketStatus::Assigned { .. } => TicketStatusKind::Assigned,
+            TicketStatus::InProgress { .. } => TicketStatusKind::InProgress,
+            TicketStatus::Resolved => TicketStatusKind::Resolved,
+            TicketStatus::Closed => TicketStatusKind::Closed,
+        }
+    }
+
+    /// The user working on the ticket, for the statuses that carry one.
+    pub fn user_id(&self) -> Option<UserId> {
+        match self {
+            TicketStatus::Assigned { user_id } | TicketStatus::InProgress { user_id } => {
+                Some(*user_id)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl TicketStatusKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketStatusKind::Open => "open",
+            TicketStatusKind::Assigned => "assigned",
+            TicketStatusKind::InProgress => "in_progress",
+            TicketStatusKind::Resolved => "resolved",
+            TicketStatusKind::Closed => "closed",
+        }
+    }
+
+    /// The ticket lifecycle is Open -> Assigned -> InProgress -> Resolved -> Closed.
+    ///
+    /// Unresolved tickets may also be closed directly or handed back to the
+    /// pool, assigned and in-progress tickets may be handed over to another
+    /// user, and resolved or closed tickets may be reopened.
+    pub fn can_transition_to(self, to: TicketStatusKind) -> bool {
+        use TicketStatusKind::*;
+        matches!(
+            (self, to),
+            (Open, Assigned | Closed)
+                | (Assigned, Open | Assigned | InProgress | Closed)
+                | (InProgress, Open | Assigned | Resolved | Closed)
+                | (Resolved, Open | Closed)
+                | (Closed, Open)
+        )
+    }
+}
+
+impl Display for TicketStatusKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TicketStatusKind {
     type Err = TicketError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "open" => Ok(TicketStatus::Open),
-            "closed" => Ok(TicketStatus::Closed),
-            "assigned" => Ok(TicketStatus::Assigned { user_id: uuid::Uuid::new_v4() }),
+            "open" => Ok(TicketStatusKind::Open),
+            "assigned" => Ok(TicketStatusKind::Assigned),
+            "in_progress" => Ok(TicketStatusKind::InProgress),
+            "resolved" => Ok(TicketStatusKind::Resolved),
+            "closed" => Ok(TicketStatusKind::Closed),
             _ => Err(TicketError::InvalidStatus),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TicketStatusKind::{self, *};
+
+    #[test]
+    fn transitions_follow_the_lifecycle() {
+        let all = [Open, Assigned, InProgress, Resolved, Closed];
+        // Allowed targets of each status, in the order of `all`
+        let table: [(TicketStatusKind, [bool; 5]); 5] = [
+            (Open, [false, true, false, false, true]),
+            (Assigned, [true, true, true, false, true]),
+            (InProgress, [true, true, false, true, true]),
+            (Resolved, [true, false, false, false, true]),
+            (Closed, [true, false, false, false, false]),
+        ];
+
+        for (from, allowed) in table {
+            for (to, expected) in all.into_iter().zip(allowed) {
+                assert_eq!(from.can_transition_to(to), expected, "{from} -> {to}");
+            }
+        }
+    }
+}
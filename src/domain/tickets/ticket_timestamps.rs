@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+/// When a ticket was created, last changed and closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TicketTimestamps {
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set while the ticket is closed, cleared when it is reopened.
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl TicketTimestamps {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            created_at: now,
+            updated_at: now,
+            closed_at: None,
+        }
+    }
+}
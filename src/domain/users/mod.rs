@@ -0,0 +1,5 @@
+pub mod repository;
+pub mod user;
+pub mod user_error;
+pub mod user_id;
+pub mod user_name;
\ No newline at end of file
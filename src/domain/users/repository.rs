@@ -0,0 +1,11 @@
+use crate::domain::error::Result;
+use crate::domain::users::user::User;
+use crate::domain::users::user_id::UserId;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn find_by_id(&self, id: UserId) -> Result<User>;
+    async fn insert(&mut self, user: User) -> Result<()>;
+    async fn save(&mut self, user: User) -> Result<()>;
+}
@@ -0,0 +1,64 @@
+use crate::domain::users::user_error::UserError;
+use crate::domain::users::user_id::UserId;
+use crate::domain::users::user_name::{UserName, UserNameError};
+use chrono::{DateTime, Utc};
+
+/// Someone tickets can be assigned to.
+///
+/// Users are deactivated instead of deleted, so the tickets they worked on
+/// keep pointing at them, but deactivated users cannot take new tickets.
+#[derive(Debug, Clone)]
+pub struct User {
+    id: UserId,
+    name: UserName,
+    active: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl User {
+    pub(crate) fn reconstruct(
+        id: UserId,
+        name: impl TryInto<UserName, Error = UserNameError>,
+        active: bool,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            name: name.try_into().unwrap(),
+            active,
+            created_at,
+        }
+    }
+
+    pub fn new(
+        name: impl TryInto<UserName, Error = UserNameError>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, UserError> {
+        Ok(Self {
+            id: UserId::new(),
+            name: name.try_into()?,
+            active: true,
+            created_at: now,
+        })
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    pub fn id(&self) -> UserId {
+        self.id
+    }
+
+    pub fn name(&self) -> String {
+        self.name.as_ref().to_string()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
@@ -0,0 +1,10 @@
+use crate::domain::users::user_name::UserNameError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum UserError {
+    #[error("User not found")]
+    NotFound,
+    #[error("User name error: {0}")]
+    UserNameError(#[from] UserNameError),
+}
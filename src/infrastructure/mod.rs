@@ -1 +1,2 @@
+pub mod outbox;
 pub mod repository;
\ No newline at end of file
@@ -0,0 +1,187 @@
+use crate::domain::error::{DomainError, Result};
+use crate::infrastructure::outbox::{EventSink, OutboxMessage};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Writes events to the application log; useful when nothing consumes them yet.
+pub struct LogEventSink;
+
+#[async_trait]
+impl EventSink for LogEventSink {
+    async fn publish(&self, message: &OutboxMessage) -> Result<()> {
+        tracing::info!(
+            event.id = %message.event_id,
+            event.type = %message.event_type,
+            aggregate.id = %message.aggregate_id,
+            payload = %message.payload,
+            "Event published"
+        );
+        Ok(())
+    }
+}
+
+/// Appends events as JSON lines to a file.
+pub struct FileEventSink {
+    path: PathBuf,
+}
+
+impl FileEventSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl EventSink for FileEventSink {
+    async fn publish(&self, message: &OutboxMessage) -> Result<()> {
+        let mut line =
+            serde_json::to_vec(message).map_err(|e| DomainError::Infrastructure(e.into()))?;
+        line.push(b'\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        file.write_all(&line)
+            .await
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        Ok(())
+    }
+}
+
+/// POSTs each event as JSON to a webhook; any non-2xx response is a failure.
+pub struct WebhookEventSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookEventSink {
+    /// Time allowed for a whole delivery, so a hung receiver cannot stall the
+    /// relay.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_timeout(url, Self::DEFAULT_TIMEOUT)
+    }
+
+    pub fn with_timeout(url: impl Into<String>, timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .build()
+            .expect("the HTTP client has a valid configuration");
+        Self {
+            client,
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookEventSink {
+    async fn publish(&self, message: &OutboxMessage) -> Result<()> {
+        self.client
+            .post(&self.url)
+            // Lets the receiver drop redeliveries
+            .header("X-Event-Id", message.event_id.to_string())
+            .json(message)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    fn message() -> OutboxMessage {
+        OutboxMessage {
+            event_id: Uuid::new_v4(),
+            aggregate_type: "ticket".to_owned(),
+            aggregate_id: Uuid::new_v4(),
+            event_type: "TicketClosed".to_owned(),
+            payload: serde_json::json!({ "type": "TicketClosed" }),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Serves `router` on a free local port and returns its URL.
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{address}/events")
+    }
+
+    #[tokio::test]
+    async fn file_sink_appends_one_json_line_per_event() {
+        let path = std::env::temp_dir().join(format!("events-{}.jsonl", Uuid::new_v4()));
+        let sink = FileEventSink::new(&path);
+        let (first, second) = (message(), message());
+
+        sink.publish(&first).await.unwrap();
+        sink.publish(&second).await.unwrap();
+
+        let written = tokio::fs::read_to_string(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        let ids = written
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["event_id"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [first.event_id.to_string(), second.event_id.to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_posts_the_event_id_and_fails_on_errors_and_timeouts() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorder = received.clone();
+        let ok = serve(Router::new().route(
+            "/events",
+            post(move |headers: HeaderMap| async move {
+                let id = headers["x-event-id"].to_str().unwrap().to_owned();
+                recorder.lock().unwrap().push(id);
+                StatusCode::NO_CONTENT
+            }),
+        ))
+        .await;
+        let failing =
+            serve(Router::new().route("/events", post(|| async { StatusCode::BAD_GATEWAY }))).await;
+        let hanging = serve(Router::new().route(
+            "/events",
+            post(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                StatusCode::NO_CONTENT
+            }),
+        ))
+        .await;
+        let event = message();
+
+        let delivered = WebhookEventSink::new(ok).publish(&event).await;
+        let rejected = WebhookEventSink::new(failing).publish(&event).await;
+        let timed_out = WebhookEventSink::with_timeout(hanging, Duration::from_millis(100))
+            .publish(&event)
+            .await;
+
+        assert!(delivered.is_ok());
+        assert_eq!(*received.lock().unwrap(), [event.event_id.to_string()]);
+        assert!(matches!(rejected, Err(DomainError::Infrastructure(_))));
+        assert!(matches!(timed_out, Err(DomainError::Infrastructure(_))));
+    }
+}
@@ -0,0 +1,54 @@
+pub mod event_sink;
+pub mod outbox_relay;
+pub mod sqlx_outbox_store;
+
+use crate::domain::error::Result;
+use crate::domain::tickets::ticket_event::RecordedTicketEvent;
+use crate::domain::tickets::ticket_id::TicketId;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+pub const TICKET_AGGREGATE: &str = "ticket";
+
+/// A domain event as it is stored in the outbox and handed to a sink.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxMessage {
+    pub event_id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl OutboxMessage {
+    pub fn from_ticket_event(ticket_id: TicketId, recorded: &RecordedTicketEvent) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            aggregate_type: TICKET_AGGREGATE.to_owned(),
+            aggregate_id: ticket_id.value(),
+            event_type: recorded.event.event_type().to_owned(),
+            payload: serde_json::to_value(&recorded.event)
+                .expect("ticket events serialize to JSON"),
+            occurred_at: recorded.occurred_at,
+        }
+    }
+}
+
+/// The read side of the outbox, used by the relay.
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// Hands up to `limit` unpublished messages to `sink` in the order they
+    /// were recorded and marks the delivered ones as published.
+    ///
+    /// Stops at the first message the sink rejects so it is retried first on
+    /// the next call. Returns the number of messages delivered.
+    async fn publish_pending(&self, sink: &dyn EventSink, limit: i64) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> Result<()>;
+}
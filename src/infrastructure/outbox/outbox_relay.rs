@@ -0,0 +1,157 @@
+use crate::infrastructure::outbox::{EventSink, OutboxStore};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Background task that moves committed events from the outbox to a sink.
+///
+/// A message is marked as published only after the sink accepted it, so a
+/// crash in between delivers it again: delivery is at-least-once.
+pub struct OutboxRelay {
+    store: Arc<dyn OutboxStore>,
+    sink: Arc<dyn EventSink>,
+    batch_size: i64,
+    poll_interval: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(store: Arc<dyn OutboxStore>, sink: Arc<dyn EventSink>) -> Self {
+        Self {
+            store,
+            sink,
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!(batch_size = self.batch_size, "Outbox relay started");
+        loop {
+            match self
+                .store
+                .publish_pending(self.sink.as_ref(), self.batch_size)
+                .await
+            {
+                // A full batch means there is probably more waiting
+                Ok(published) if published as i64 == self.batch_size => continue,
+                Ok(published) => {
+                    if published > 0 {
+                        tracing::debug!(published, "Outbox events published");
+                    }
+                }
+                Err(e) => tracing::error!(error = ?e, "Outbox relay failed"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::usecase::tickets::{create_ticket, NewTicket};
+    use crate::domain::actor::Actor;
+    use crate::domain::clock::FixedClock;
+    use crate::domain::error::{DomainError, Result};
+    use crate::domain::tickets::ticket_audit::AuditContext;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+    use crate::infrastructure::outbox::OutboxMessage;
+    use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+    use async_trait::async_trait;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Rejects the first `failures` deliveries, then records every message.
+    struct FlakySink {
+        failures: Mutex<usize>,
+        attempts: Mutex<Vec<Uuid>>,
+        delivered: Mutex<Vec<Uuid>>,
+    }
+
+    impl FlakySink {
+        fn failing(failures: usize) -> Arc<Self> {
+            Arc::new(Self {
+                failures: Mutex::new(failures),
+                attempts: Mutex::new(Vec::new()),
+                delivered: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn delivered(&self) -> Vec<Uuid> {
+            self.delivered.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        async fn publish(&self, message: &OutboxMessage) -> Result<()> {
+            self.attempts.lock().unwrap().push(message.event_id);
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(DomainError::RepositoryError("sink unavailable".to_owned()));
+            }
+            self.delivered.lock().unwrap().push(message.event_id);
+            Ok(())
+        }
+    }
+
+    async fn create_tickets(fac: &InMemoryUowFactory, count: usize) {
+        let clock = FixedClock(Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap());
+        let actor = Actor::new("tester", vec![Actor::ADMIN.to_owned()]);
+        for _ in 0..count {
+            let new = NewTicket {
+                title: "Printer is jammed".to_owned(),
+                description: "Paper stuck in tray 2".to_owned(),
+                priority: TicketPriority::default(),
+                due_date: None,
+            };
+            create_ticket(fac, &clock, &actor, AuditContext::default(), new)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn rejected_messages_are_delivered_again_before_later_ones() {
+        let fac = InMemoryUowFactory::default();
+        create_tickets(&fac, 2).await;
+        let sink = FlakySink::failing(0);
+        fac.publish_pending(sink.as_ref(), 10).await.unwrap();
+        let events = sink.delivered();
+        create_tickets(&fac, 2).await;
+        // The first new message is rejected once
+        *sink.failures.lock().unwrap() = 1;
+
+        let rejected = fac.publish_pending(sink.as_ref(), 10).await.unwrap();
+        let retried = fac.publish_pending(sink.as_ref(), 10).await.unwrap();
+        let drained = fac.publish_pending(sink.as_ref(), 10).await.unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!((rejected, retried, drained), (0, 2, 0));
+        let attempts = sink.attempts.lock().unwrap().clone();
+        assert_eq!(attempts[2], attempts[3], "the rejected message goes first");
+        assert_eq!(sink.delivered().len(), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn relay_keeps_polling_until_the_sink_accepts() {
+        let fac = InMemoryUowFactory::default();
+        create_tickets(&fac, 1).await;
+        let sink = FlakySink::failing(2);
+        let relay = OutboxRelay::new(Arc::new(fac.clone()), sink.clone());
+
+        let task = tokio::spawn(relay.run());
+        let delivered = tokio::time::timeout(Duration::from_secs(60), async {
+            while sink.delivered().is_empty() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+        task.abort();
+
+        assert!(delivered.is_ok(), "the relay never delivered the event");
+        assert_eq!(sink.attempts.lock().unwrap().len(), 3);
+        assert_eq!(sink.delivered().len(), 1);
+    }
+}
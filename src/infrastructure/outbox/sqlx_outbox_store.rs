@@ -0,0 +1,134 @@
+use crate::domain::error::Result;
+use crate::infrastructure::outbox::{EventSink, OutboxMessage, OutboxStore};
+use crate::infrastructure::repository::sqlx_ticket_repository::repository_error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Records messages in the caller's transaction, so they are committed or
+/// rolled back together with the change that produced them.
+pub async fn append(conn: &mut PgConnection, messages: &[OutboxMessage]) -> Result<()> {
+    for message in messages {
+        sqlx::query!(
+            r#"
+            INSERT INTO outbox_events
+            (event_id, aggregate_type, aggregate_id, event_type, payload, occurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            message.event_id,
+            message.aggregate_type,
+            message.aggregate_id,
+            message.event_type,
+            message.payload,
+            message.occurred_at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(repository_error)?;
+    }
+    Ok(())
+}
+
+pub struct SqlxOutboxStore {
+    pool: sqlx::PgPool,
+    claim_duration: Duration,
+}
+
+impl SqlxOutboxStore {
+    /// How long a relay may take to deliver a batch before another relay
+    /// takes the unpublished rest of it over.
+    pub const DEFAULT_CLAIM_DURATION: Duration = Duration::from_secs(300);
+
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            claim_duration: Self::DEFAULT_CLAIM_DURATION,
+        }
+    }
+}
+
+#[async_trait]
+impl OutboxStore for SqlxOutboxStore {
+    async fn publish_pending(&self, sink: &dyn EventSink, limit: i64) -> Result<usize> {
+        // Claim in a statement of its own, so no row lock or connection is
+        // held while the sink talks to the network. SKIP LOCKED lets several
+        // relays claim at the same time without blocking each other.
+        let mut rows = sqlx::query_as!(
+            OutboxRow,
+            r#"
+            UPDATE outbox_events
+            SET claimed_until = now() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id
+                FROM outbox_events
+                WHERE published_at IS NULL
+                  AND (claimed_until IS NULL OR claimed_until < now())
+                ORDER BY id
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, event_id, aggregate_type, aggregate_id, event_type, payload, occurred_at
+            "#,
+            limit,
+            self.claim_duration.as_secs_f64(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        rows.sort_by_key(|row| row.id);
+
+        let claimed = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+        let mut published = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id = row.id;
+            let message = OutboxMessage::from(row);
+            if let Err(e) = sink.publish(&message).await {
+                tracing::warn!(error = ?e, event.id = %message.event_id, "Event delivery failed");
+                break;
+            }
+            published.push(id);
+        }
+
+        // Release the rest of the claim so the failed message is retried
+        // first on the next call
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET published_at = CASE WHEN id = ANY($1) THEN now() END,
+                claimed_until = NULL
+            WHERE id = ANY($2)
+            "#,
+            &published,
+            &claimed,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(repository_error)?;
+        Ok(published.len())
+    }
+}
+
+struct OutboxRow {
+    id: i64,
+    event_id: Uuid,
+    aggregate_type: String,
+    aggregate_id: Uuid,
+    event_type: String,
+    payload: serde_json::Value,
+    occurred_at: DateTime<Utc>,
+}
+
+impl From<OutboxRow> for OutboxMessage {
+    fn from(row: OutboxRow) -> Self {
+        Self {
+            event_id: row.event_id,
+            aggregate_type: row.aggregate_type,
+            aggregate_id: row.aggregate_id,
+            event_type: row.event_type,
+            payload: row.payload,
+            occurred_at: row.occurred_at,
+        }
+    }
+}
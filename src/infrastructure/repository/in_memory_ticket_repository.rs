@@ -0,0 +1,681 @@
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::comments::comment::Comment;
+use crate::domain::comments::comment_error::CommentError;
+use crate::domain::comments::comment_id::CommentId;
+use crate::domain::comments::repository::CommentRepository;
+use crate::domain::error::{DomainError, Result};
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use crate::domain::idempotency::idempotency_record::IdempotencyRecord;
+use crate::domain::idempotency::repository::IdempotencyRepository;
+use crate::domain::tickets::repository::{
+    TicketRepository, UnitOfWork, UowFactory, UowFnc, UowOptions,
+};
+use crate::domain::tickets::ticket::{Ticket, TicketId};
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry, TicketSnapshot};
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_query::{TicketPage, TicketQuery};
+use crate::domain::tickets::ticket_search::{TicketSearch, TicketSearchHit};
+use crate::domain::users::repository::UserRepository;
+use crate::domain::users::user::User;
+use crate::domain::users::user_error::UserError;
+use crate::domain::users::user_id::UserId;
+use crate::infrastructure::outbox::{EventSink, OutboxMessage, OutboxStore};
+use async_trait::async_trait;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+type SharedStore = Arc<Mutex<InMemoryStore>>;
+type IdempotencyRecords = HashMap<(String, IdempotencyKey), IdempotencyRecord>;
+
+#[derive(Default)]
+struct InMemoryStore {
+    tickets: HashMap<TicketId, Ticket>,
+    comments: HashMap<CommentId, Comment>,
+    users: HashMap<UserId, User>,
+    idempotency_records: IdempotencyRecords,
+    outbox: Vec<OutboxEntry>,
+    audit_log: Vec<TicketAuditEntry>,
+}
+
+struct OutboxEntry {
+    message: OutboxMessage,
+    published: bool,
+}
+
+/// Keeps tickets in process memory, for tests and local development.
+///
+/// A unit of work copies a ticket from the store the first time it reads or
+/// writes it, so reading it again returns the same version, and buffers every
+/// other write. Lists and searches see the tickets committed so far together
+/// with those copies. On commit the writes are applied only if none of the
+/// tickets written were committed by another unit of work in the meantime,
+/// and on `Err` they are discarded. Every isolation level behaves the same,
+/// statement timeouts are ignored, and read-only units of work reject writes.
+/// Comments and users are not versioned, so the last unit of work to commit
+/// wins; an idempotency key stored by another unit of work first is a
+/// transaction conflict.
+#[derive(Clone)]
+pub struct InMemoryUowFactory {
+    store: SharedStore,
+    /// Timestamps audit entries.
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for InMemoryUowFactory {
+    fn default() -> Self {
+        Self::new(Arc::new(SystemClock))
+    }
+}
+
+impl InMemoryUowFactory {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            store: SharedStore::default(),
+            clock,
+        }
+    }
+}
+
+#[async_trait]
+impl UowFactory for InMemoryUowFactory {
+    async fn execute_raw(&self, options: UowOptions, f: UowFnc) -> Result<Box<dyn Any + Send>> {
+        let tx_shared = Arc::new(Mutex::new(InMemoryTransaction {
+            store: self.store.clone(),
+            clock: self.clock.clone(),
+            tickets: HashMap::new(),
+            base_versions: HashMap::new(),
+            comments: HashMap::new(),
+            users: HashMap::new(),
+            idempotency_records: HashMap::new(),
+            outbox: Vec::new(),
+            audit_log: Vec::new(),
+            audit: options.audit,
+            read_only: options.read_only,
+        }));
+        let uow = Box::new(InMemoryUnitOfWork {
+            tx: tx_shared.clone(),
+        });
+
+        match f(uow).await {
+            Ok(value) => {
+                if let Ok(tx_mutex) = Arc::try_unwrap(tx_shared) {
+                    tx_mutex.into_inner().commit().await?;
+                    tracing::info!("Transaction committed");
+                    Ok(value)
+                } else {
+                    Err(DomainError::Infrastructure(
+                        "Transaction reference leak".into(),
+                    ))
+                }
+            }
+            // Dropping the transaction discards its writes
+            Err(e) => {
+                tracing::error!(error= ?e, "Transaction rollback");
+                Err(e)
+            }
+        }
+    }
+}
+
+struct InMemoryTransaction {
+    store: SharedStore,
+    clock: Arc<dyn Clock>,
+    /// Tickets read or written by this transaction, as it sees them.
+    tickets: HashMap<TicketId, Ticket>,
+    /// Version of each written ticket when this transaction first wrote it,
+    /// or `None` for tickets inserted by this transaction.
+    base_versions: HashMap<TicketId, Option<i64>>,
+    /// Comments inserted or saved by this transaction, or `None` if deleted.
+    comments: HashMap<CommentId, Option<Comment>>,
+    /// Users inserted or saved by this transaction.
+    users: HashMap<UserId, User>,
+    /// Idempotency records inserted by this transaction.
+    idempotency_records: IdempotencyRecords,
+    outbox: Vec<OutboxMessage>,
+    /// Audit entries written by this transaction.
+    audit_log: Vec<TicketAuditEntry>,
+    audit: AuditContext,
+    read_only: bool,
+}
+
+impl InMemoryTransaction {
+    fn ensure_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(DomainError::RepositoryError(
+                "Cannot write in a read-only transaction".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The ticket as this transaction sees it, copying it from the store on
+    /// first access.
+    async fn ticket(&mut self, id: TicketId) -> Option<&Ticket> {
+        if !self.tickets.contains_key(&id) {
+            let ticket = self.store.lock().await.tickets.get(&id).cloned()?;
+            self.tickets.insert(id, ticket);
+        }
+        self.tickets.get(&id)
+    }
+
+    /// Committed tickets, replaced by this transaction's copies, followed by
+    /// the tickets it inserted.
+    fn visible_tickets<'a>(&'a self, store: &'a InMemoryStore) -> impl Iterator<Item = &'a Ticket> {
+        let committed = store
+            .tickets
+            .iter()
+            .map(|(id, ticket)| self.tickets.get(id).unwrap_or(ticket));
+        let inserted = self
+            .tickets
+            .iter()
+            .filter(|(id, _)| !store.tickets.contains_key(id))
+            .map(|(_, ticket)| ticket);
+        committed.chain(inserted)
+    }
+
+    /// The comment as this transaction sees it.
+    async fn comment(&self, ticket_id: TicketId, id: CommentId) -> Option<Comment> {
+        let comment = match self.comments.get(&id) {
+            Some(written) => written.clone(),
+            None => self.store.lock().await.comments.get(&id).cloned(),
+        };
+        comment.filter(|c| c.ticket_id() == ticket_id)
+    }
+
+    /// The user as this transaction sees it.
+    async fn user(&self, id: UserId) -> Option<User> {
+        match self.users.get(&id) {
+            Some(written) => Some(written.clone()),
+            None => self.store.lock().await.users.get(&id).cloned(),
+        }
+    }
+
+    fn record_audit(&mut self, previous: Option<TicketSnapshot>, ticket: &Ticket) {
+        let entry = TicketAuditEntry {
+            ticket_id: ticket.id(),
+            previous,
+            current: TicketSnapshot::from(ticket),
+            actor: self.audit.actor.clone(),
+            trace_id: self.audit.trace_id.clone(),
+            recorded_at: self.clock.now(),
+        };
+        self.audit_log.push(entry);
+    }
+
+    async fn commit(mut self) -> Result<()> {
+        let mut store = self.store.lock().await;
+        for (id, base_version) in &self.base_versions {
+            let current_version = store.tickets.get(id).map(|t| t.version());
+            if current_version != *base_version {
+                return Err(match base_version {
+                    None => DomainError::RepositoryError(format!("Duplicate ticket id: {id}")),
+                    Some(_) => DomainError::ConcurrentModification,
+                });
+            }
+        }
+        for id in self.idempotency_records.keys() {
+            if store.idempotency_records.contains_key(id) {
+                return Err(DomainError::TransactionConflict(format!(
+                    "Idempotency key {:?} was stored by a concurrent request",
+                    id.1.as_ref()
+                )));
+            }
+        }
+        for id in self.base_versions.keys() {
+            let ticket = self.tickets.remove(id).unwrap();
+            store.tickets.insert(*id, ticket);
+        }
+        for (id, comment) in self.comments {
+            match comment {
+                Some(comment) => store.comments.insert(id, comment),
+                None => store.comments.remove(&id),
+            };
+        }
+        store.users.extend(self.users);
+        store.idempotency_records.extend(self.idempotency_records);
+        store
+            .outbox
+            .extend(self.outbox.into_iter().map(|message| OutboxEntry {
+                message,
+                published: false,
+            }));
+        store.audit_log.append(&mut self.audit_log);
+        Ok(())
+    }
+}
+
+pub struct InMemoryUnitOfWork {
+    tx: Arc<Mutex<InMemoryTransaction>>,
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    fn ticket_repo(&self) -> Box<dyn TicketRepository + '_> {
+        Box::new(InMemoryTicketRepository { tx: &self.tx })
+    }
+
+    fn comment_repo(&self) -> Box<dyn CommentRepository + '_> {
+        Box::new(InMemoryCommentRepository { tx: &self.tx })
+    }
+
+    fn user_repo(&self) -> Box<dyn UserRepository + '_> {
+        Box::new(InMemoryUserRepository { tx: &self.tx })
+    }
+
+    fn idempotency_repo(&self) -> Box<dyn IdempotencyRepository + '_> {
+        Box::new(InMemoryIdempotencyRepository { tx: &self.tx })
+    }
+
+    async fn commit(self: Box<Self>) -> Result<()> {
+        let tx_mutex = Arc::into_inner(self.tx).unwrap();
+        tx_mutex.into_inner().commit().await
+    }
+}
+
+pub struct InMemoryTicketRepository<'a> {
+    tx: &'a Mutex<InMemoryTransaction>,
+}
+
+#[async_trait]
+impl<'a> TicketRepository for InMemoryTicketRepository<'a> {
+    async fn find_by_id(&self, id: TicketId) -> Result<Ticket> {
+        let mut tx = self.tx.lock().await;
+        Ok(tx.ticket(id).await.cloned().ok_or(TicketError::NotFound)?)
+    }
+
+    async fn insert(&mut self, mut ticket: Ticket) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        if tx.ticket(ticket.id()).await.is_some() {
+            return Err(DomainError::RepositoryError(format!(
+                "Duplicate ticket id: {}",
+                ticket.id()
+            )));
+        }
+        tx.base_versions.insert(ticket.id(), None);
+        let events = ticket.take_events();
+        tx.outbox.extend(
+            events
+                .iter()
+                .map(|event| OutboxMessage::from_ticket_event(ticket.id(), event)),
+        );
+        tx.record_audit(None, &ticket);
+        tx.tickets.insert(ticket.id(), ticket);
+        Ok(())
+    }
+
+    async fn save(&mut self, mut ticket: Ticket) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        let previous = match tx.ticket(ticket.id()).await {
+            Some(current) if current.version() == ticket.version() => TicketSnapshot::from(current),
+            _ => return Err(DomainError::ConcurrentModification),
+        };
+        tx.base_versions
+            .entry(ticket.id())
+            .or_insert(Some(ticket.version()));
+        ticket.increment_version();
+        let events = ticket.take_events();
+        tx.outbox.extend(
+            events
+                .iter()
+                .map(|event| OutboxMessage::from_ticket_event(ticket.id(), event)),
+        );
+        tx.record_audit(Some(previous), &ticket);
+        tx.tickets.insert(ticket.id(), ticket);
+        Ok(())
+    }
+
+    async fn list(&self, query: &TicketQuery) -> Result<TicketPage> {
+        let tx = self.tx.lock().await;
+        let store = tx.store.lock().await;
+        let matches = tx
+            .visible_tickets(&store)
+            .filter(|t| query.matches(t))
+            .cloned()
+            .collect();
+        Ok(query.paginate(matches))
+    }
+
+    async fn search(&self, search: &TicketSearch) -> Result<Vec<TicketSearchHit>> {
+        let tx = self.tx.lock().await;
+        let store = tx.store.lock().await;
+        let hits = tx
+            .visible_tickets(&store)
+            .filter_map(|t| search.hit(t))
+            .collect();
+        Ok(search.rank(hits))
+    }
+
+    async fn history(&self, id: TicketId) -> Result<Vec<TicketAuditEntry>> {
+        let tx = self.tx.lock().await;
+        let store = tx.store.lock().await;
+        Ok(store
+            .audit_log
+            .iter()
+            .chain(&tx.audit_log)
+            .filter(|entry| entry.ticket_id == id)
+            .cloned()
+            .collect())
+    }
+}
+
+pub struct InMemoryCommentRepository<'a> {
+    tx: &'a Mutex<InMemoryTransaction>,
+}
+
+#[async_trait]
+impl<'a> CommentRepository for InMemoryCommentRepository<'a> {
+    async fn find_by_id(&self, ticket_id: TicketId, id: CommentId) -> Result<Comment> {
+        let tx = self.tx.lock().await;
+        Ok(tx
+            .comment(ticket_id, id)
+            .await
+            .ok_or(CommentError::NotFound)?)
+    }
+
+    async fn list(&self, ticket_id: TicketId) -> Result<Vec<Comment>> {
+        let tx = self.tx.lock().await;
+        let store = tx.store.lock().await;
+        let committed = store
+            .comments
+            .iter()
+            .filter(|(id, _)| !tx.comments.contains_key(id))
+            .map(|(_, comment)| comment);
+        let written = tx.comments.values().flatten();
+        let mut comments = committed
+            .chain(written)
+            .filter(|c| c.ticket_id() == ticket_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        comments.sort_by_key(|c| (c.created_at(), c.id().value()));
+        Ok(comments)
+    }
+
+    async fn insert(&mut self, comment: Comment) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        let exists = match tx.comments.get(&comment.id()) {
+            Some(written) => written.is_some(),
+            None => tx.store.lock().await.comments.contains_key(&comment.id()),
+        };
+        if exists {
+            return Err(DomainError::RepositoryError(format!(
+                "Duplicate comment id: {}",
+                comment.id()
+            )));
+        }
+        tx.comments.insert(comment.id(), Some(comment));
+        Ok(())
+    }
+
+    async fn save(&mut self, comment: Comment) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        if tx
+            .comment(comment.ticket_id(), comment.id())
+            .await
+            .is_none()
+        {
+            return Err(CommentError::NotFound.into());
+        }
+        tx.comments.insert(comment.id(), Some(comment));
+        Ok(())
+    }
+
+    async fn delete(&mut self, ticket_id: TicketId, id: CommentId) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        if tx.comment(ticket_id, id).await.is_none() {
+            return Err(CommentError::NotFound.into());
+        }
+        tx.comments.insert(id, None);
+        Ok(())
+    }
+}
+
+pub struct InMemoryUserRepository<'a> {
+    tx: &'a Mutex<InMemoryTransaction>,
+}
+
+#[async_trait]
+impl<'a> UserRepository for InMemoryUserRepository<'a> {
+    async fn find_by_id(&self, id: UserId) -> Result<User> {
+        let tx = self.tx.lock().await;
+        Ok(tx.user(id).await.ok_or(UserError::NotFound)?)
+    }
+
+    async fn insert(&mut self, user: User) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        if tx.user(user.id()).await.is_some() {
+            return Err(DomainError::RepositoryError(format!(
+                "Duplicate user id: {}",
+                user.id()
+            )));
+        }
+        tx.users.insert(user.id(), user);
+        Ok(())
+    }
+
+    async fn save(&mut self, user: User) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        if tx.user(user.id()).await.is_none() {
+            return Err(UserError::NotFound.into());
+        }
+        tx.users.insert(user.id(), user);
+        Ok(())
+    }
+}
+
+pub struct InMemoryIdempotencyRepository<'a> {
+    tx: &'a Mutex<InMemoryTransaction>,
+}
+
+#[async_trait]
+impl<'a> IdempotencyRepository for InMemoryIdempotencyRepository<'a> {
+    async fn find(&self, actor: &str, key: &IdempotencyKey) -> Result<Option<IdempotencyRecord>> {
+        let tx = self.tx.lock().await;
+        let id = (actor.to_owned(), key.clone());
+        match tx.idempotency_records.get(&id) {
+            Some(record) => Ok(Some(record.clone())),
+            None => Ok(tx.store.lock().await.idempotency_records.get(&id).cloned()),
+        }
+    }
+
+    async fn insert(&mut self, record: IdempotencyRecord) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        tx.ensure_writable()?;
+        let id = (record.actor.clone(), record.key.clone());
+        let stored = tx.idempotency_records.contains_key(&id)
+            || tx.store.lock().await.idempotency_records.contains_key(&id);
+        if stored {
+            return Err(DomainError::TransactionConflict(format!(
+                "Idempotency key {:?} was stored by a concurrent request",
+                record.key.as_ref()
+            )));
+        }
+        tx.idempotency_records.insert(id, record);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryUowFactory {
+    async fn publish_pending(&self, sink: &dyn EventSink, limit: i64) -> Result<usize> {
+        // Deliver without holding the lock; entries are never removed, so
+        // their positions stay valid
+        let pending = {
+            let store = self.store.lock().await;
+            store
+                .outbox
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| !entry.published)
+                .take(limit as usize)
+                .map(|(position, entry)| (position, entry.message.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let mut published = Vec::with_capacity(pending.len());
+        for (position, message) in pending {
+            if let Err(e) = sink.publish(&message).await {
+                tracing::warn!(error = ?e, event.id = %message.event_id, "Event delivery failed");
+                break;
+            }
+            published.push(position);
+        }
+
+        let mut store = self.store.lock().await;
+        for position in &published {
+            store.outbox[*position].published = true;
+        }
+        Ok(published.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use crate::domain::clock::FixedClock;
+    use crate::domain::tickets::repository::UowFactoryExt;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+    use crate::domain::tickets::ticket_status::TicketStatus;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, hour, 0, 0).unwrap()
+    }
+
+    fn ticket() -> Ticket {
+        let reporter = Actor::new("tester", vec![]);
+        Ticket::new(
+            "Printer is jammed",
+            "Paper stuck in tray 2",
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            at(9),
+        )
+        .unwrap()
+    }
+
+    async fn insert(fac: &InMemoryUowFactory, ticket: Ticket) -> Result<()> {
+        fac.execute_with_options(UowOptions::default(), async move |uow| {
+            uow.ticket_repo().insert(ticket).await
+        })
+        .await
+    }
+
+    async fn find(fac: &InMemoryUowFactory, id: TicketId) -> Result<Ticket> {
+        fac.execute_with_options(UowOptions::read_only(), async move |uow| {
+            uow.ticket_repo().find_by_id(id).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn writes_are_discarded_when_the_unit_of_work_fails() {
+        let fac = InMemoryUowFactory::default();
+        let ticket = ticket();
+        let id = ticket.id();
+
+        let result: Result<()> = fac
+            .execute_with_options(UowOptions::default(), async move |uow| {
+                uow.ticket_repo().insert(ticket).await?;
+                Err(DomainError::RepositoryError("failed later".to_owned()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::RepositoryError(_))));
+        assert!(matches!(
+            find(&fac, id).await,
+            Err(DomainError::Ticket(TicketError::NotFound))
+        ));
+        assert!(fac.store.lock().await.outbox.is_empty());
+        assert!(fac.store.lock().await.audit_log.is_empty());
+    }
+
+    #[tokio::test]
+    async fn saving_a_stale_version_is_a_concurrent_modification() {
+        let fac = InMemoryUowFactory::default();
+        let ticket = ticket();
+        let id = ticket.id();
+        insert(&fac, ticket).await.unwrap();
+        let stale = find(&fac, id).await.unwrap();
+
+        let mut first = stale.clone();
+        first.close(at(10)).unwrap();
+        fac.execute_with_options(UowOptions::default(), async move |uow| {
+            uow.ticket_repo().save(first).await
+        })
+        .await
+        .unwrap();
+        let mut second = stale;
+        second.change_title("Printer is on fire", at(11)).unwrap();
+        let result = fac
+            .execute_with_options(UowOptions::default(), async move |uow| {
+                uow.ticket_repo().save(second).await
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ConcurrentModification)));
+        assert_eq!(find(&fac, id).await.unwrap().title(), "Printer is jammed");
+    }
+
+    #[tokio::test]
+    async fn a_commit_fails_if_another_unit_of_work_committed_the_same_ticket_first() {
+        let fac = InMemoryUowFactory::default();
+        let ticket = ticket();
+        let id = ticket.id();
+        insert(&fac, ticket).await.unwrap();
+
+        let other = fac.clone();
+        let result = fac
+            .execute_with_options(UowOptions::default(), async move |uow| {
+                let mut repo = uow.ticket_repo();
+                let mut ticket = repo.find_by_id(id).await?;
+                let mut concurrent = find(&other, id).await?;
+                concurrent.close(at(10))?;
+                other
+                    .execute_with_options(UowOptions::default(), async move |uow| {
+                        uow.ticket_repo().save(concurrent).await
+                    })
+                    .await?;
+
+                // Reads repeat the copy taken first
+                assert_eq!(repo.find_by_id(id).await?.version(), ticket.version());
+                ticket.change_title("Printer is on fire", at(11))?;
+                repo.save(ticket).await
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ConcurrentModification)));
+        let ticket = find(&fac, id).await.unwrap();
+        assert_eq!(ticket.title(), "Printer is jammed");
+        assert_eq!(ticket.status(), TicketStatus::Closed);
+    }
+
+    #[tokio::test]
+    async fn audit_entries_are_timestamped_by_the_injected_clock() {
+        let fac = InMemoryUowFactory::new(Arc::new(FixedClock(at(12))));
+        let ticket = ticket();
+        let id = ticket.id();
+        insert(&fac, ticket).await.unwrap();
+
+        let history = fac
+            .execute_with_options(UowOptions::read_only(), async move |uow| {
+                uow.ticket_repo().history(id).await
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].recorded_at, at(12));
+    }
+}
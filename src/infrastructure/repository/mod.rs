@@ -1 +1,8 @@
-pub mod sqlx_ticket_repository;
\ No newline at end of file
+pub mod in_memory_ticket_repository;
+pub mod sqlx_comment_repository;
+pub mod sqlx_event_store_ticket_repository;
+pub mod sqlx_idempotency_repository;
+pub mod sqlx_ticket_audit;
+pub mod sqlx_ticket_repository;
+pub mod sqlx_user_repository;
+pub mod ticket_status_codec;
@@ -0,0 +1,144 @@
+use crate::domain::comments::comment::Comment;
+use crate::domain::comments::comment_error::CommentError;
+use crate::domain::comments::comment_id::CommentId;
+use crate::domain::comments::repository::CommentRepository;
+use crate::domain::error::Result;
+use crate::domain::tickets::ticket_id::TicketId;
+use crate::infrastructure::repository::sqlx_ticket_repository::repository_error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqlxCommentRepository<'a> {
+    tx: &'a Mutex<Transaction<'static, Postgres>>,
+}
+
+impl<'a> SqlxCommentRepository<'a> {
+    pub(super) fn new(tx: &'a Mutex<Transaction<'static, Postgres>>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl<'a> CommentRepository for SqlxCommentRepository<'a> {
+    async fn find_by_id(&self, ticket_id: TicketId, id: CommentId) -> Result<Comment> {
+        let mut tx = self.tx.lock().await;
+        let row = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, ticket_id, author, body, created_at, updated_at
+            FROM ticket_comments
+            WHERE id = $1 AND ticket_id = $2
+            "#,
+            id.value(),
+            ticket_id.value(),
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(repository_error)?
+        .ok_or(CommentError::NotFound)?;
+
+        Ok(row.into())
+    }
+
+    async fn list(&self, ticket_id: TicketId) -> Result<Vec<Comment>> {
+        let mut tx = self.tx.lock().await;
+        let rows = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, ticket_id, author, body, created_at, updated_at
+            FROM ticket_comments
+            WHERE ticket_id = $1
+            ORDER BY created_at, id
+            "#,
+            ticket_id.value(),
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        Ok(rows.into_iter().map(Comment::from).collect())
+    }
+
+    async fn insert(&mut self, comment: Comment) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        sqlx::query!(
+            r#"
+            INSERT INTO ticket_comments (id, ticket_id, author, body, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            comment.id().value(),
+            comment.ticket_id().value(),
+            comment.author(),
+            comment.body(),
+            comment.created_at(),
+            comment.updated_at(),
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn save(&mut self, comment: Comment) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        let result = sqlx::query!(
+            r#"
+            UPDATE ticket_comments
+            SET body = $1, updated_at = $2
+            WHERE id = $3 AND ticket_id = $4
+            "#,
+            comment.body(),
+            comment.updated_at(),
+            comment.id().value(),
+            comment.ticket_id().value(),
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        if result.rows_affected() == 0 {
+            return Err(CommentError::NotFound.into());
+        }
+        Ok(())
+    }
+
+    async fn delete(&mut self, ticket_id: TicketId, id: CommentId) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        let result = sqlx::query!(
+            "DELETE FROM ticket_comments WHERE id = $1 AND ticket_id = $2",
+            id.value(),
+            ticket_id.value(),
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        if result.rows_affected() == 0 {
+            return Err(CommentError::NotFound.into());
+        }
+        Ok(())
+    }
+}
+
+struct CommentRow {
+    id: Uuid,
+    ticket_id: Uuid,
+    author: Option<String>,
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CommentRow> for Comment {
+    fn from(row: CommentRow) -> Self {
+        Comment::reconstruct(
+            CommentId::from(row.id),
+            TicketId::from(row.ticket_id),
+            row.author,
+            row.body,
+            row.created_at,
+            row.updated_at,
+        )
+    }
+}
@@ -0,0 +1,513 @@
+//! Ticket persistence as an append-only stream of events per ticket.
+//!
+//! No current state is stored; it is rebuilt by folding the stream, and the
+//! position of the last event is the ticket version. Two writers appending at
+//! the same position collide on the primary key, which is reported as a
+//! concurrent modification.
+//!
+//! Lists and searches filter a projection of the current state, kept in step
+//! with the streams in the same transaction, and fold only the streams of the
+//! tickets they return.
+
+use crate::domain::error::{DomainError, Result};
+use crate::domain::tickets::repository::TicketRepository;
+use crate::domain::tickets::ticket::{Ticket, TicketId};
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry, TicketSnapshot};
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_event::{RecordedTicketEvent, TicketEvent};
+use crate::domain::tickets::ticket_query::{TicketPage, TicketQuery};
+use crate::domain::tickets::ticket_search::{TicketSearch, TicketSearchHit};
+use crate::infrastructure::outbox::{sqlx_outbox_store, OutboxMessage};
+use crate::infrastructure::repository::sqlx_ticket_repository::{
+    escape_like, label_names, repository_error,
+};
+use crate::infrastructure::repository::{sqlx_ticket_audit, ticket_status_codec};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqlxEventStoreTicketRepository<'a> {
+    tx: &'a Mutex<Transaction<'static, Postgres>>,
+    audit: &'a AuditContext,
+}
+
+impl<'a> SqlxEventStoreTicketRepository<'a> {
+    pub(super) fn new(
+        tx: &'a Mutex<Transaction<'static, Postgres>>,
+        audit: &'a AuditContext,
+    ) -> Self {
+        Self { tx, audit }
+    }
+}
+
+#[async_trait]
+impl<'a> TicketRepository for SqlxEventStoreTicketRepository<'a> {
+    async fn find_by_id(&self, id: TicketId) -> Result<Ticket> {
+        let mut tx = self.tx.lock().await;
+        Ok(load(&mut tx, id).await?.ok_or(TicketError::NotFound)?)
+    }
+
+    async fn insert(&mut self, mut ticket: Ticket) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        let events = ticket.take_events();
+        append(&mut tx, ticket.id(), 0, &events).await?;
+        let current = TicketSnapshot {
+            version: events.len() as i64 - 1,
+            ..TicketSnapshot::from(&ticket)
+        };
+        project(&mut tx, &ticket, current.version).await?;
+        sqlx_ticket_audit::append(&mut tx, ticket.id(), None, &current, self.audit).await
+    }
+
+    async fn save(&mut self, mut ticket: Ticket) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        let events = ticket.take_events();
+        if events.is_empty() {
+            // Nothing to append, but the caller still relies on the version check
+            let head = sqlx::query_scalar!(
+                "SELECT max(version) FROM ticket_events WHERE ticket_id = $1",
+                ticket.id().value()
+            )
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(repository_error)?;
+            if head != Some(ticket.version()) {
+                return Err(DomainError::ConcurrentModification);
+            }
+            return Ok(());
+        }
+        let previous = load(&mut tx, ticket.id())
+            .await?
+            .ok_or(TicketError::NotFound)?;
+        append(&mut tx, ticket.id(), ticket.version() + 1, &events).await?;
+        let current = TicketSnapshot {
+            version: ticket.version() + events.len() as i64,
+            ..TicketSnapshot::from(&ticket)
+        };
+        project(&mut tx, &ticket, current.version).await?;
+        sqlx_ticket_audit::append(
+            &mut tx,
+            ticket.id(),
+            Some(&TicketSnapshot::from(&previous)),
+            &current,
+            self.audit,
+        )
+        .await
+    }
+
+    async fn list(&self, query: &TicketQuery) -> Result<TicketPage> {
+        let mut tx = self.tx.lock().await;
+        let labels_any = label_names(&query.labels_any);
+        let labels_all = label_names(&query.labels_all);
+
+        // The total and the page come from the same filtered set; one extra
+        // id tells whether there is a next page
+        let page = sqlx::query!(
+            r#"
+            WITH matches AS (
+                SELECT ticket_id, created_at
+                FROM ticket_projections
+                WHERE ($1::text IS NULL OR lower(status) = $1)
+                  AND ($2::uuid IS NULL OR assignee = $2)
+                  AND ($3::text IS NULL OR title ILIKE '%' || $3 || '%')
+                  AND ($4::text IS NULL OR priority = $4)
+                  AND ($5::bool IS NULL OR (
+                        due_date IS NOT NULL AND due_date < $6
+                        AND lower(status) NOT IN ('resolved', 'closed')
+                      ) = $5)
+                  AND (cardinality($7::text[]) = 0 OR labels && $7)
+                  AND labels @> $8
+            )
+            SELECT
+                (SELECT count(*) FROM matches) AS "total!",
+                ARRAY(
+                    SELECT ticket_id FROM matches
+                    WHERE $9::timestamptz IS NULL OR (created_at, ticket_id) > ($9, $10)
+                    ORDER BY created_at, ticket_id
+                    LIMIT $11
+                ) AS "ids!"
+            "#,
+            query.status.map(|s| s.as_str()),
+            query.assignee.map(|a| a.value()),
+            query.title.as_deref().map(escape_like),
+            query.priority.map(|p| p.as_str()),
+            query.overdue,
+            query.as_of,
+            &labels_any,
+            &labels_all,
+            query.cursor.map(|c| c.created_at),
+            query.cursor.map(|c| c.id),
+            query.limit + 1,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        let items = load_many(&mut tx, &page.ids).await?;
+        Ok(query.page(items, page.total))
+    }
+
+    async fn search(&self, search: &TicketSearch) -> Result<Vec<TicketSearchHit>> {
+        let mut tx = self.tx.lock().await;
+        let patterns = search
+            .terms()
+            .iter()
+            .map(|term| format!("%{}%", escape_like(term)))
+            .collect::<Vec<_>>();
+        let longest = patterns
+            .iter()
+            .max_by_key(|pattern| pattern.chars().count())
+            .cloned()
+            .unwrap_or_default();
+        let title_options = format!(
+            "StartSel={}, StopSel={}, HighlightAll=true",
+            TicketSearch::HIGHLIGHT_START,
+            TicketSearch::HIGHLIGHT_END,
+        );
+        let snippet_options = format!(
+            "StartSel={}, StopSel={}, MaxFragments=2, MaxWords=20, MinWords=8, \
+             FragmentDelimiter=\" … \"",
+            TicketSearch::HIGHLIGHT_START,
+            TicketSearch::HIGHLIGHT_END,
+        );
+
+        // Ranked the same way as the row store
+        let rows = sqlx::query!(
+            r#"
+            SELECT ticket_id,
+                   (ts_rank(search_vector, q) + greatest(
+                       word_similarity($1, title),
+                       word_similarity($1, description) * 0.4
+                   ))::real AS "rank!",
+                   CASE WHEN search_vector @@ q
+                       THEN ts_headline('english', html_escape(title), q, $4)
+                   END AS title_highlight,
+                   CASE WHEN search_vector @@ q
+                       THEN ts_headline('english', html_escape(description), q, $5)
+                   END AS snippet
+            FROM ticket_projections, websearch_to_tsquery('english', $1) AS q
+            WHERE search_vector @@ q
+               OR ((title ILIKE $2 OR description ILIKE $2)
+                   AND title || ' ' || description ILIKE ALL ($3))
+            ORDER BY "rank!" DESC, ticket_id
+            LIMIT $6
+            "#,
+            search.text,
+            longest,
+            &patterns,
+            title_options,
+            snippet_options,
+            search.limit,
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        let ids = rows.iter().map(|row| row.ticket_id).collect::<Vec<_>>();
+        let tickets = load_many(&mut tx, &ids).await?;
+        Ok(rows
+            .into_iter()
+            .zip(tickets)
+            .map(|(row, ticket)| TicketSearchHit {
+                title: row
+                    .title_highlight
+                    .unwrap_or_else(|| search.highlight(&ticket.title())),
+                snippet: row
+                    .snippet
+                    .unwrap_or_else(|| search.snippet(&ticket.description())),
+                rank: row.rank,
+                ticket,
+            })
+            .collect())
+    }
+
+    async fn history(&self, id: TicketId) -> Result<Vec<TicketAuditEntry>> {
+        let mut tx = self.tx.lock().await;
+        sqlx_ticket_audit::history(&mut tx, id).await
+    }
+}
+
+async fn load(tx: &mut Transaction<'static, Postgres>, id: TicketId) -> Result<Option<Ticket>> {
+    let rows = sqlx::query_as!(
+        EventRow,
+        r#"
+        SELECT ticket_id, payload, occurred_at
+        FROM ticket_events
+        WHERE ticket_id = $1
+        ORDER BY version
+        "#,
+        id.value()
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+    replay(id, rows).map(Some)
+}
+
+/// Folds the streams of `ids`, returning the tickets in the same order.
+async fn load_many(tx: &mut Transaction<'static, Postgres>, ids: &[Uuid]) -> Result<Vec<Ticket>> {
+    let rows = sqlx::query_as!(
+        EventRow,
+        r#"
+        SELECT ticket_id, payload, occurred_at
+        FROM ticket_events
+        WHERE ticket_id = ANY($1)
+        ORDER BY ticket_id, version
+        "#,
+        ids
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+
+    let mut streams: HashMap<Uuid, Vec<EventRow>> = HashMap::new();
+    for row in rows {
+        streams.entry(row.ticket_id).or_default().push(row);
+    }
+    ids.iter()
+        .map(|id| {
+            let rows = streams.remove(id).ok_or_else(|| {
+                DomainError::RepositoryError(format!("Projected ticket {id} has no events"))
+            })?;
+            replay(TicketId::from(*id), rows)
+        })
+        .collect()
+}
+
+/// Writes the current state of `ticket`, at stream position `version`, to
+/// its projection.
+async fn project(
+    tx: &mut Transaction<'static, Postgres>,
+    ticket: &Ticket,
+    version: i64,
+) -> Result<()> {
+    let (status, user_id) = ticket_status_codec::encode(&ticket.status());
+    let assignee = user_id.or(ticket.assignee().map(|a| a.value()));
+    sqlx::query!(
+        r#"
+        INSERT INTO ticket_projections
+        (ticket_id, version, title, description, status, assignee, priority, due_date, labels,
+         created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (ticket_id) DO UPDATE SET
+            version = EXCLUDED.version,
+            title = EXCLUDED.title,
+            description = EXCLUDED.description,
+            status = EXCLUDED.status,
+            assignee = EXCLUDED.assignee,
+            priority = EXCLUDED.priority,
+            due_date = EXCLUDED.due_date,
+            labels = EXCLUDED.labels
+        "#,
+        ticket.id().value(),
+        version,
+        ticket.title(),
+        ticket.description(),
+        status,
+        assignee,
+        ticket.priority().as_str(),
+        ticket.due_date(),
+        &ticket.labels(),
+        ticket.timestamps().created_at,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+    Ok(())
+}
+
+/// Projects the streams that have no projection yet, such as those appended
+/// before projections were kept. Returns how many were projected.
+pub async fn project_missing(pool: &PgPool) -> Result<usize> {
+    let mut tx = pool.begin().await.map_err(repository_error)?;
+    let ids = sqlx::query_scalar!(
+        r#"
+        SELECT ticket_id
+        FROM ticket_events e
+        WHERE version = 0
+          AND NOT EXISTS (SELECT 1 FROM ticket_projections p WHERE p.ticket_id = e.ticket_id)
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(repository_error)?;
+
+    for ticket in load_many(&mut tx, &ids).await? {
+        project(&mut tx, &ticket, ticket.version()).await?;
+    }
+    tx.commit().await.map_err(repository_error)?;
+    Ok(ids.len())
+}
+
+/// Appends `events` to the stream of `ticket_id` starting at `first_version`,
+/// and records them in the outbox of the same transaction.
+async fn append(
+    tx: &mut Transaction<'static, Postgres>,
+    ticket_id: TicketId,
+    first_version: i64,
+    events: &[RecordedTicketEvent],
+) -> Result<()> {
+    for (version, recorded) in (first_version..).zip(events) {
+        let payload = serde_json::to_value(&recorded.event)
+            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        sqlx::query!(
+            r#"
+            INSERT INTO ticket_events (ticket_id, version, event_type, payload, occurred_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            ticket_id.value(),
+            version,
+            recorded.event.event_type(),
+            payload,
+            recorded.occurred_at,
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            if is_unique_violation(&e) {
+                DomainError::ConcurrentModification
+            } else {
+                repository_error(e)
+            }
+        })?;
+    }
+
+    let messages = events
+        .iter()
+        .map(|event| OutboxMessage::from_ticket_event(ticket_id, event))
+        .collect::<Vec<_>>();
+    sqlx_outbox_store::append(tx, &messages).await
+}
+
+/// Someone else already appended an event at the same stream position.
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .is_some_and(|db| db.kind() == sqlx::error::ErrorKind::UniqueViolation)
+}
+
+fn replay(id: TicketId, rows: Vec<EventRow>) -> Result<Ticket> {
+    let events = rows
+        .into_iter()
+        .map(|row| {
+            serde_json::from_value::<TicketEvent>(row.payload).map(|event| RecordedTicketEvent {
+                event,
+                occurred_at: row.occurred_at,
+            })
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+    Ticket::replay(id, events).ok_or_else(|| {
+        DomainError::RepositoryError(format!("Event stream of ticket {id} has no creation event"))
+    })
+}
+
+struct EventRow {
+    ticket_id: Uuid,
+    payload: serde_json::Value,
+    occurred_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+    use crate::domain::users::user::User;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, hour, 0, 0).unwrap()
+    }
+
+    /// The rows `append` writes for `events`.
+    fn rows(id: TicketId, events: &[RecordedTicketEvent]) -> Vec<EventRow> {
+        events
+            .iter()
+            .map(|recorded| EventRow {
+                ticket_id: id.value(),
+                payload: serde_json::to_value(&recorded.event).unwrap(),
+                occurred_at: recorded.occurred_at,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replaying_the_stored_events_rebuilds_the_ticket() {
+        let reporter = Actor::new("tester", vec![]);
+        let user = User::new("Alice", at(9)).unwrap();
+        let mut ticket = Ticket::new(
+            "Printer is jammed",
+            "Paper stuck in tray 2",
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            at(9),
+        )
+        .unwrap();
+        ticket
+            .change_title("Printer on floor 3 is jammed", at(10))
+            .unwrap();
+        ticket.change_priority(TicketPriority::High, at(10));
+        ticket
+            .change_due_date(Some(at(9).date_naive()), at(10))
+            .unwrap();
+        ticket.add_label("hardware", at(10)).unwrap();
+        ticket.add_label("urgent", at(10)).unwrap();
+        ticket.remove_label("urgent", at(10)).unwrap();
+        ticket.assign(&user, at(11)).unwrap();
+        ticket.start_progress(at(11)).unwrap();
+        ticket.resolve(at(12)).unwrap();
+        ticket.close(at(13)).unwrap();
+        let events = ticket.take_events();
+
+        let replayed = replay(ticket.id(), rows(ticket.id(), &events)).unwrap();
+
+        assert_eq!(replayed.version(), events.len() as i64 - 1);
+        assert_eq!(
+            TicketSnapshot::from(&replayed),
+            TicketSnapshot {
+                version: replayed.version(),
+                ..TicketSnapshot::from(&ticket)
+            }
+        );
+        assert_eq!(replayed.status(), ticket.status());
+        assert_eq!(replayed.reporter(), Some("tester"));
+        assert_eq!(replayed.timestamps(), ticket.timestamps());
+    }
+
+    #[test]
+    fn each_event_after_the_creation_moves_the_version_forward() {
+        let reporter = Actor::new("tester", vec![]);
+        let mut ticket = Ticket::new(
+            "Printer is jammed",
+            "Paper stuck in tray 2",
+            None,
+            &reporter,
+            TicketPriority::default(),
+            None,
+            at(9),
+        )
+        .unwrap();
+        let created = ticket.take_events();
+        ticket.close(at(10)).unwrap();
+        ticket.reopen(at(11)).unwrap();
+        let events = [created, ticket.take_events()].concat();
+
+        for len in 1..=events.len() {
+            let replayed = replay(ticket.id(), rows(ticket.id(), &events[..len])).unwrap();
+            assert_eq!(replayed.version(), len as i64 - 1);
+        }
+        assert!(matches!(
+            replay(ticket.id(), rows(ticket.id(), &events[1..])),
+            Err(DomainError::RepositoryError(_))
+        ));
+    }
+}
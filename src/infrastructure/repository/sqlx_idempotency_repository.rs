@@ -0,0 +1,104 @@
+use crate::domain::error::{DomainError, Result};
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use crate::domain::idempotency::idempotency_record::{IdempotencyRecord, StoredResponse};
+use crate::domain::idempotency::repository::IdempotencyRepository;
+use crate::infrastructure::repository::sqlx_ticket_repository::repository_error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{Postgres, Transaction};
+use tokio::sync::Mutex;
+
+pub struct SqlxIdempotencyRepository<'a> {
+    tx: &'a Mutex<Transaction<'static, Postgres>>,
+}
+
+impl<'a> SqlxIdempotencyRepository<'a> {
+    pub(super) fn new(tx: &'a Mutex<Transaction<'static, Postgres>>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl<'a> IdempotencyRepository for SqlxIdempotencyRepository<'a> {
+    async fn find(&self, actor: &str, key: &IdempotencyKey) -> Result<Option<IdempotencyRecord>> {
+        let mut tx = self.tx.lock().await;
+        let row = sqlx::query_as!(
+            IdempotencyRow,
+            r#"
+            SELECT actor, key, request_hash, response_status,
+                   response_headers AS "response_headers: Json<Vec<(String, String)>>",
+                   response_body, created_at
+            FROM idempotency_keys
+            WHERE actor = $1 AND key = $2
+            "#,
+            actor,
+            key.as_ref(),
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        row.map(IdempotencyRecord::try_from).transpose()
+    }
+
+    async fn insert(&mut self, record: IdempotencyRecord) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        // Waits for a concurrent transaction holding the same key to finish
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO idempotency_keys
+            (actor, key, request_hash, response_status, response_headers, response_body,
+             created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (actor, key) DO NOTHING
+            "#,
+            record.actor,
+            record.key.as_ref(),
+            record.request_hash,
+            record.response.status as i16,
+            Json(&record.response.headers) as _,
+            record.response.body,
+            record.created_at,
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::TransactionConflict(format!(
+                "Idempotency key {:?} was stored by a concurrent request",
+                record.key.as_ref()
+            )));
+        }
+        Ok(())
+    }
+}
+
+struct IdempotencyRow {
+    actor: String,
+    key: String,
+    request_hash: String,
+    response_status: i16,
+    response_headers: Json<Vec<(String, String)>>,
+    response_body: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<IdempotencyRow> for IdempotencyRecord {
+    type Error = DomainError;
+
+    fn try_from(row: IdempotencyRow) -> Result<Self> {
+        Ok(IdempotencyRecord {
+            actor: row.actor,
+            key: IdempotencyKey::try_from(row.key)
+                .map_err(|e| DomainError::RepositoryError(e.to_string()))?,
+            request_hash: row.request_hash,
+            response: StoredResponse {
+                status: row.response_status as u16,
+                headers: row.response_headers.0,
+                body: row.response_body,
+            },
+            created_at: row.created_at,
+        })
+    }
+}
@@ -0,0 +1,89 @@
+//! The `ticket_audit` table, shared by the Postgres ticket repositories.
+
+use crate::domain::error::{DomainError, Result};
+use crate::domain::tickets::ticket::TicketId;
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry, TicketSnapshot};
+use crate::infrastructure::repository::sqlx_ticket_repository::repository_error;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Records a change in the caller's transaction, so the entry exists exactly
+/// when the change was committed.
+pub(super) async fn append(
+    conn: &mut PgConnection,
+    ticket_id: TicketId,
+    previous: Option<&TicketSnapshot>,
+    current: &TicketSnapshot,
+    audit: &AuditContext,
+) -> Result<()> {
+    let previous = previous.map(to_json).transpose()?;
+    sqlx::query!(
+        r#"
+        INSERT INTO ticket_audit (ticket_id, previous, current, actor, trace_id)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        ticket_id.value(),
+        previous,
+        to_json(current)?,
+        audit.actor,
+        audit.trace_id,
+    )
+    .execute(conn)
+    .await
+    .map_err(repository_error)?;
+    Ok(())
+}
+
+pub(super) async fn history(
+    conn: &mut PgConnection,
+    ticket_id: TicketId,
+) -> Result<Vec<TicketAuditEntry>> {
+    let rows = sqlx::query_as!(
+        AuditRow,
+        r#"
+        SELECT ticket_id, previous, current, actor, trace_id, recorded_at
+        FROM ticket_audit
+        WHERE ticket_id = $1
+        ORDER BY id
+        "#,
+        ticket_id.value()
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(repository_error)?;
+
+    rows.into_iter().map(TicketAuditEntry::try_from).collect()
+}
+
+fn to_json(snapshot: &TicketSnapshot) -> Result<serde_json::Value> {
+    serde_json::to_value(snapshot).map_err(|e| DomainError::Infrastructure(e.into()))
+}
+
+fn from_json(value: serde_json::Value) -> Result<TicketSnapshot> {
+    serde_json::from_value(value).map_err(|e| DomainError::RepositoryError(e.to_string()))
+}
+
+struct AuditRow {
+    ticket_id: Uuid,
+    previous: Option<serde_json::Value>,
+    current: serde_json::Value,
+    actor: Option<String>,
+    trace_id: Option<String>,
+    recorded_at: DateTime<Utc>,
+}
+
+impl TryFrom<AuditRow> for TicketAuditEntry {
+    type Error = DomainError;
+
+    fn try_from(row: AuditRow) -> Result<Self> {
+        Ok(TicketAuditEntry {
+            ticket_id: TicketId::from(row.ticket_id),
+            previous: row.previous.map(from_json).transpose()?,
+            current: from_json(row.current)?,
+            actor: row.actor,
+            trace_id: row.trace_id,
+            recorded_at: row.recorded_at,
+        })
+    }
+}
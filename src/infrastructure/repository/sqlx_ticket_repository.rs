@@ -1,41 +1,86 @@
+use crate::domain::comments::repository::CommentRepository;
 use crate::domain::error::{DomainError, Result};
-use crate::domain::tickets::repository::{TicketRepository, UnitOfWork, UowFactory, UowFnc};
+use crate::domain::idempotency::repository::IdempotencyRepository;
+use crate::domain::tickets::repository::{
+    IsolationLevel, TicketRepository, UnitOfWork, UowFactory, UowFnc, UowOptions,
+};
 use crate::domain::tickets::ticket::{Ticket, TicketId};
+use crate::domain::tickets::ticket_audit::{AuditContext, TicketAuditEntry, TicketSnapshot};
 use crate::domain::tickets::ticket_error::TicketError;
-use crate::domain::tickets::ticket_status::TicketStatus;
+use crate::domain::tickets::ticket_label::TicketLabel;
+use crate::domain::tickets::ticket_query::{TicketPage, TicketQuery};
+use crate::domain::tickets::ticket_search::{TicketSearch, TicketSearchHit};
+use crate::domain::tickets::ticket_timestamps::TicketTimestamps;
+use crate::domain::users::repository::UserRepository;
+use crate::domain::users::user_id::UserId;
+use crate::infrastructure::outbox::{sqlx_outbox_store, OutboxMessage};
+use crate::infrastructure::repository::sqlx_comment_repository::SqlxCommentRepository;
+use crate::infrastructure::repository::sqlx_event_store_ticket_repository::SqlxEventStoreTicketRepository;
+use crate::infrastructure::repository::sqlx_idempotency_repository::SqlxIdempotencyRepository;
+use crate::infrastructure::repository::sqlx_user_repository::SqlxUserRepository;
+use crate::infrastructure::repository::{sqlx_ticket_audit, ticket_status_codec};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::{Postgres, Transaction};
 use std::any::Any;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::info_span;
 use uuid::Uuid;
 
+/// How the Postgres units of work keep tickets.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TicketStorage {
+    /// One row per ticket in `tickets`, updated in place.
+    #[default]
+    Rows,
+    /// Append-only streams in `ticket_events`, folded on every read, with
+    /// their current state projected into `ticket_projections` for lists and
+    /// searches.
+    Events,
+}
+
 pub struct SqlxUowFactory {
     pool: sqlx::PgPool,
+    storage: TicketStorage,
 }
 
 impl SqlxUowFactory {
     pub fn new(pool: sqlx::PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            storage: TicketStorage::Rows,
+        }
+    }
+
+    pub fn with_event_store(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            storage: TicketStorage::Events,
+        }
     }
 }
 
 #[async_trait]
 impl UowFactory for SqlxUowFactory {
-    async fn execute_raw(&self, f: UowFnc) -> Result<Box<dyn Any + Send>> {
-        let span = info_span!("db.transaction", db.system = "postgresql");
+    async fn execute_raw(&self, options: UowOptions, f: UowFnc) -> Result<Box<dyn Any + Send>> {
+        let span = info_span!(
+            "db.transaction",
+            db.system = "postgresql",
+            db.read_only = options.read_only,
+            db.isolation_level = ?options.isolation_level,
+        );
         let _enter = span.enter();
 
         // 1. Begin transaction
-        let tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        let mut tx = self.pool.begin().await.map_err(infrastructure_error)?;
+        apply_options(&mut tx, &options).await?;
         let tx_shared = Arc::new(Mutex::new(tx));
         let uow = Box::new(SqlxUnitOfWork {
             tx: tx_shared.clone(),
+            storage: self.storage,
+            audit: options.audit.clone(),
         });
 
         // 2. Execute closure(use case Logic)
@@ -47,9 +92,7 @@ impl UowFactory for SqlxUowFactory {
                 // Assume that transaction reference is not leaked
                 if let Ok(tx_mutex) = Arc::try_unwrap(tx_shared) {
                     let tx = tx_mutex.into_inner();
-                    tx.commit()
-                        .await
-                        .map_err(|e| DomainError::Infrastructure(e.into()))?;
+                    tx.commit().await.map_err(infrastructure_error)?;
                     tracing::info!("Transaction committed");
                     Ok(value)
                 } else {
@@ -68,26 +111,80 @@ impl UowFactory for SqlxUowFactory {
     }
 }
 
+/// Applies the unit of work options; must run before any other statement.
+async fn apply_options(
+    tx: &mut Transaction<'static, Postgres>,
+    options: &UowOptions,
+) -> Result<()> {
+    if options.isolation_level.is_some() || options.read_only {
+        let isolation_level = match options.isolation_level {
+            Some(IsolationLevel::ReadCommitted) => " ISOLATION LEVEL READ COMMITTED",
+            Some(IsolationLevel::RepeatableRead) => " ISOLATION LEVEL REPEATABLE READ",
+            Some(IsolationLevel::Serializable) => " ISOLATION LEVEL SERIALIZABLE",
+            None => "",
+        };
+        let access_mode = if options.read_only {
+            " READ ONLY"
+        } else {
+            " READ WRITE"
+        };
+        sqlx::query(&format!("SET TRANSACTION{isolation_level}{access_mode}"))
+            .execute(&mut **tx)
+            .await
+            .map_err(infrastructure_error)?;
+    }
+    if let Some(timeout) = options.statement_timeout {
+        // SET LOCAL takes no bind parameters, set_config(.., true) is its equivalent
+        sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+            .bind(format!("{}ms", timeout.as_millis()))
+            .execute(&mut **tx)
+            .await
+            .map_err(infrastructure_error)?;
+    }
+    Ok(())
+}
+
 pub struct SqlxTicketRepository<'a> {
     tx: &'a Mutex<Transaction<'static, Postgres>>,
+    audit: &'a AuditContext,
 }
 
 pub struct SqlxUnitOfWork {
     tx: Arc<Mutex<Transaction<'static, Postgres>>>,
+    storage: TicketStorage,
+    audit: AuditContext,
 }
 
 #[async_trait]
 impl UnitOfWork for SqlxUnitOfWork {
     fn ticket_repo(&self) -> Box<dyn TicketRepository + '_> {
-        Box::new(SqlxTicketRepository { tx: &self.tx })
+        match self.storage {
+            TicketStorage::Rows => Box::new(SqlxTicketRepository {
+                tx: &self.tx,
+                audit: &self.audit,
+            }),
+            TicketStorage::Events => {
+                Box::new(SqlxEventStoreTicketRepository::new(&self.tx, &self.audit))
+            }
+        }
+    }
+
+    fn comment_repo(&self) -> Box<dyn CommentRepository + '_> {
+        Box::new(SqlxCommentRepository::new(&self.tx))
+    }
+
+    fn user_repo(&self) -> Box<dyn UserRepository + '_> {
+        Box::new(SqlxUserRepository::new(&self.tx))
+    }
+
+    fn idempotency_repo(&self) -> Box<dyn IdempotencyRepository + '_> {
+        Box::new(SqlxIdempotencyRepository::new(&self.tx))
     }
 
     async fn commit(self: Box<Self>) -> Result<()> {
         let tx_mutex = Arc::into_inner(self.tx).unwrap();
         let tx = tx_mutex.into_inner();
-        tx.commit()
-            .await
-            .map_err(|e| DomainError::Infrastructure(e.into()))?;
+        tx.commit().await.map_err(infrastructure_error)?;
         Ok(())
     }
 }
@@ -99,7 +196,12 @@ impl<'a> TicketRepository for SqlxTicketRepository<'a> {
         let row = sqlx::query_as!(
             TicketRow,
             r#"
-            SELECT id, title, description, status, assignee, version
+            SELECT id, title, description, status, assignee, reporter, priority, due_date, version,
+                   created_at, updated_at, closed_at,
+                   ARRAY(
+                       SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                       WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                   ) AS "labels!"
             FROM tickets
             WHERE id = $1
             "#,
@@ -107,82 +209,700 @@ impl<'a> TicketRepository for SqlxTicketRepository<'a> {
         )
         .fetch_optional(&mut **tx)
         .await
-        .map_err(|e| DomainError::RepositoryError(e.to_string()))?
+        .map_err(repository_error)?
         .ok_or(TicketError::NotFound)?;
 
-        Ok(Ticket::reconstruct(
-            TicketId::from(row.id),
-            row.title,
-            row.description,
-            row.status.parse().map_err(|_| TicketError::InvalidStatus)?,
-            row.assignee,
-            row.version,
-        ))
+        row.try_into()
     }
-    async fn insert(&mut self, ticket: Ticket) -> Result<()> {
+    async fn insert(&mut self, mut ticket: Ticket) -> Result<()> {
         let mut tx = self.tx.lock().await;
-        let status = ticket.status().to_string();
+        let (status, user_id) = ticket_status_codec::encode(&ticket.status());
+        let assignee = user_id.or(ticket.assignee().map(|a| a.value()));
+        let timestamps = ticket.timestamps();
 
         sqlx::query!(
             r#"
             INSERT INTO tickets
-            (id, title, description, status, assignee, version)
-            VALUES ($1, $2, $3, $4, $5, 0)
+            (id, title, description, status, assignee, priority, due_date, version,
+             created_at, updated_at, closed_at, reporter)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 0, $8, $9, $10, $11)
             "#,
             ticket.id().value(),
             ticket.title(),
             ticket.description(),
             status,
-            ticket.assignee(),
+            assignee,
+            ticket.priority().as_str(),
+            ticket.due_date(),
+            timestamps.created_at,
+            timestamps.updated_at,
+            timestamps.closed_at,
+            ticket.reporter(),
         )
         .execute(&mut **tx)
         .await
-        .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        .map_err(repository_error)?;
+        if !ticket.labels().is_empty() {
+            sync_labels(&mut tx, &ticket).await?;
+        }
+        let current = TicketSnapshot::from(&ticket);
+        sqlx_ticket_audit::append(&mut tx, ticket.id(), None, &current, self.audit).await?;
+        append_events(&mut tx, &mut ticket).await?;
         Ok(())
     }
 
-    async fn save(&mut self, ticket: Ticket) -> Result<()> {
+    async fn save(&mut self, mut ticket: Ticket) -> Result<()> {
         let mut tx = self.tx.lock().await;
-        let status = match ticket.status() {
-            TicketStatus::Open => "open",
-            TicketStatus::Assigned { user_id: _ } => "assigned",
-            TicketStatus::Closed => "closed",
-        };
+        let (status, user_id) = ticket_status_codec::encode(&ticket.status());
+        let assignee = user_id.or(ticket.assignee().map(|a| a.value()));
+        let timestamps = ticket.timestamps();
 
-        let result = sqlx::query!(
+        // The locked row still holds the values before the update, for the audit
+        let previous = sqlx::query_as!(
+            TicketRow,
             r#"
+            WITH previous AS (
+                SELECT id, title, description, status, assignee, reporter, priority, due_date, version,
+                       created_at, updated_at, closed_at,
+                       ARRAY(
+                           SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                           WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                       ) AS labels
+                FROM tickets
+                WHERE id = $5 AND version = $6
+                FOR UPDATE
+            )
             UPDATE tickets
             SET
                 title = $1,
                 description = $2,
                 status = $3,
                 assignee = $4,
-                version = version + 1
-            WHERE id = $5 AND version = $6
+                version = tickets.version + 1,
+                updated_at = $7,
+                closed_at = $8,
+                priority = $9,
+                due_date = $10
+            FROM previous
+            WHERE tickets.id = previous.id
+            RETURNING
+                previous.id AS "id!",
+                previous.title AS "title!",
+                previous.description AS "description!",
+                previous.status AS "status!",
+                previous.assignee,
+                previous.reporter,
+                previous.priority AS "priority!",
+                previous.due_date,
+                previous.version AS "version!",
+                previous.created_at AS "created_at!",
+                previous.updated_at AS "updated_at!",
+                previous.closed_at,
+                previous.labels AS "labels!"
             "#,
             ticket.title(),
             ticket.description(),
             status,
-            ticket.assignee(),
+            assignee,
             ticket.id().value(),
             ticket.version(),
+            timestamps.updated_at,
+            timestamps.closed_at,
+            ticket.priority().as_str(),
+            ticket.due_date(),
         )
-        .execute(&mut **tx)
+        .fetch_optional(&mut **tx)
         .await
-        .map_err(|e| DomainError::RepositoryError(e.to_string()))?;
+        .map_err(repository_error)?
+        .ok_or(DomainError::ConcurrentModification)?;
 
-        if result.rows_affected() == 0 {
+        record_save(&mut tx, previous, &mut ticket, self.audit).await
+    }
+
+    async fn list(&self, query: &TicketQuery) -> Result<TicketPage> {
+        let mut tx = self.tx.lock().await;
+        let labels_any = label_names(&query.labels_any);
+        let labels_all = label_names(&query.labels_all);
+
+        // The total and the page come from the same filtered set; one extra
+        // id tells whether there is a next page
+        let page = sqlx::query!(
+            r#"
+            WITH matches AS (
+                SELECT id, created_at
+                FROM tickets
+                WHERE ($1::text IS NULL OR lower(status) = $1)
+                  AND ($2::uuid IS NULL OR assignee = $2)
+                  AND ($3::text IS NULL OR title ILIKE '%' || $3 || '%')
+                  AND ($4::text IS NULL OR priority = $4)
+                  AND ($5::bool IS NULL OR (
+                        due_date IS NOT NULL AND due_date < $6
+                        AND lower(status) NOT IN ('resolved', 'closed')
+                      ) = $5)
+                  AND (cardinality($7::text[]) = 0 OR EXISTS (
+                        SELECT 1 FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                        WHERE tl.ticket_id = tickets.id AND l.name = ANY($7)
+                      ))
+                  AND (
+                        SELECT count(*) FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                        WHERE tl.ticket_id = tickets.id AND l.name = ANY($8)
+                      ) = cardinality($8)
+            )
+            SELECT
+                (SELECT count(*) FROM matches) AS "total!",
+                ARRAY(
+                    SELECT id FROM matches
+                    WHERE $9::timestamptz IS NULL OR (created_at, id) > ($9, $10)
+                    ORDER BY created_at, id
+                    LIMIT $11
+                ) AS "ids!"
+            "#,
+            query.status.map(|s| s.as_str()),
+            query.assignee.map(|a| a.value()),
+            query.title.as_deref().map(escape_like),
+            query.priority.map(|p| p.as_str()),
+            query.overdue,
+            query.as_of,
+            &labels_any,
+            &labels_all,
+            query.cursor.map(|c| c.created_at),
+            query.cursor.map(|c| c.id),
+            query.limit + 1,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        let rows = sqlx::query_as!(
+            TicketRow,
+            r#"
+            SELECT id, title, description, status, assignee, reporter, priority, due_date, version,
+                   created_at, updated_at, closed_at,
+                   ARRAY(
+                       SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                       WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                   ) AS "labels!"
+            FROM tickets
+            WHERE id = ANY($1)
+            ORDER BY created_at, id
+            "#,
+            &page.ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        let items = rows
+            .into_iter()
+            .map(Ticket::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(query.page(items, page.total))
+    }
+
+    async fn search(&self, search: &TicketSearch) -> Result<Vec<TicketSearchHit>> {
+        let mut tx = self.tx.lock().await;
+        let terms = search.terms();
+        let patterns = terms
+            .iter()
+            .map(|term| format!("%{}%", escape_like(term)))
+            .collect::<Vec<_>>();
+        // Only the longest term can use the trigram indexes; the rest are
+        // checked on the rows it narrows down to
+        let longest = patterns
+            .iter()
+            .max_by_key(|pattern| pattern.chars().count())
+            .cloned()
+            .unwrap_or_default();
+        let title_options = format!(
+            "StartSel={}, StopSel={}, HighlightAll=true",
+            TicketSearch::HIGHLIGHT_START,
+            TicketSearch::HIGHLIGHT_END,
+        );
+        let snippet_options = format!(
+            "StartSel={}, StopSel={}, MaxFragments=2, MaxWords=20, MinWords=8, \
+             FragmentDelimiter=\" … \"",
+            TicketSearch::HIGHLIGHT_START,
+            TicketSearch::HIGHLIGHT_END,
+        );
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, title, description, status, assignee, reporter, priority, due_date, version,
+                   created_at, updated_at, closed_at,
+                   ARRAY(
+                       SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                       WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                   ) AS "labels!",
+                   (ts_rank(search_vector, q) + greatest(
+                       word_similarity($1, title),
+                       word_similarity($1, description) * 0.4
+                   ))::real AS "rank!",
+                   CASE WHEN search_vector @@ q
+                       THEN ts_headline('english', html_escape(title), q, $4)
+                   END AS title_highlight,
+                   CASE WHEN search_vector @@ q
+                       THEN ts_headline('english', html_escape(description), q, $5)
+                   END AS snippet
+            FROM tickets, websearch_to_tsquery('english', $1) AS q
+            WHERE search_vector @@ q
+               OR ((title ILIKE $2 OR description ILIKE $2)
+                   AND title || ' ' || description ILIKE ALL ($3))
+            ORDER BY "rank!" DESC, id
+            LIMIT $6
+            "#,
+            search.text,
+            longest,
+            &patterns,
+            title_options,
+            snippet_options,
+            search.limit,
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        rows.into_iter()
+            .map(|row| {
+                // Substring matches have no headline, as the text search
+                // parser did not find the terms
+                let title = row
+                    .title_highlight
+                    .unwrap_or_else(|| search.highlight(&row.title));
+                let snippet = row
+                    .snippet
+                    .unwrap_or_else(|| search.snippet(&row.description));
+                let ticket = Ticket::try_from(TicketRow {
+                    id: row.id,
+                    title: row.title,
+                    description: row.description,
+                    status: row.status,
+                    assignee: row.assignee,
+                    reporter: row.reporter,
+                    priority: row.priority,
+                    due_date: row.due_date,
+                    version: row.version,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                    closed_at: row.closed_at,
+                    labels: row.labels,
+                })?;
+                Ok(TicketSearchHit {
+                    ticket,
+                    rank: row.rank,
+                    title,
+                    snippet,
+                })
+            })
+            .collect()
+    }
+
+    async fn history(&self, id: TicketId) -> Result<Vec<TicketAuditEntry>> {
+        let mut tx = self.tx.lock().await;
+        sqlx_ticket_audit::history(&mut tx, id).await
+    }
+
+    async fn find_many(&self, ids: &[TicketId]) -> Result<Vec<Ticket>> {
+        let mut tx = self.tx.lock().await;
+        let ids = ids.iter().map(|id| id.value()).collect::<Vec<_>>();
+        let rows = sqlx::query_as!(
+            TicketRow,
+            r#"
+            SELECT id, title, description, status, assignee, reporter, priority, due_date, version,
+                   created_at, updated_at, closed_at,
+                   ARRAY(
+                       SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                       WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                   ) AS "labels!"
+            FROM tickets
+            WHERE id = ANY($1)
+            "#,
+            &ids
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+
+        rows.into_iter().map(Ticket::try_from).collect()
+    }
+
+    /// Updates all rows in one statement; the audit entries, label changes
+    /// and events are then written per ticket as in `save`.
+    async fn save_many(&mut self, tickets: Vec<Ticket>) -> Result<()> {
+        if tickets.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.tx.lock().await;
+        let mut ids = Vec::with_capacity(tickets.len());
+        let mut versions = Vec::with_capacity(tickets.len());
+        let mut titles = Vec::with_capacity(tickets.len());
+        let mut descriptions = Vec::with_capacity(tickets.len());
+        let mut statuses = Vec::with_capacity(tickets.len());
+        let mut assignees = Vec::with_capacity(tickets.len());
+        let mut priorities = Vec::with_capacity(tickets.len());
+        let mut due_dates = Vec::with_capacity(tickets.len());
+        let mut updated_ats = Vec::with_capacity(tickets.len());
+        let mut closed_ats = Vec::with_capacity(tickets.len());
+        for ticket in &tickets {
+            let (status, user_id) = ticket_status_codec::encode(&ticket.status());
+            let timestamps = ticket.timestamps();
+            ids.push(ticket.id().value());
+            versions.push(ticket.version());
+            titles.push(ticket.title());
+            descriptions.push(ticket.description());
+            statuses.push(status);
+            assignees.push(user_id.or(ticket.assignee().map(|a| a.value())));
+            priorities.push(ticket.priority().as_str());
+            due_dates.push(ticket.due_date());
+            updated_ats.push(timestamps.updated_at);
+            closed_ats.push(timestamps.closed_at);
+        }
+
+        // As in `save`, the locked rows still hold the values before the update
+        let previous_rows = sqlx::query_as!(
+            TicketRow,
+            r#"
+            WITH input AS (
+                SELECT *
+                FROM UNNEST(
+                    $1::uuid[], $2::int8[], $3::text[], $4::text[], $5::text[], $6::uuid[],
+                    $7::text[], $8::date[], $9::timestamptz[], $10::timestamptz[]
+                ) AS input(id, version, title, description, status, assignee, priority,
+                           due_date, updated_at, closed_at)
+            ),
+            previous AS (
+                SELECT tickets.id, tickets.title, tickets.description, tickets.status,
+                       tickets.assignee, tickets.reporter, tickets.priority, tickets.due_date,
+                       tickets.version, tickets.created_at, tickets.updated_at,
+                       tickets.closed_at,
+                       ARRAY(
+                           SELECT l.name FROM ticket_labels tl JOIN labels l ON l.id = tl.label_id
+                           WHERE tl.ticket_id = tickets.id ORDER BY l.name
+                       ) AS labels
+                FROM tickets
+                JOIN input ON input.id = tickets.id AND input.version = tickets.version
+                FOR UPDATE OF tickets
+            )
+            UPDATE tickets
+            SET
+                title = input.title,
+                description = input.description,
+                status = input.status,
+                assignee = input.assignee,
+                version = tickets.version + 1,
+                updated_at = input.updated_at,
+                closed_at = input.closed_at,
+                priority = input.priority,
+                due_date = input.due_date
+            FROM previous
+            JOIN input ON input.id = previous.id
+            WHERE tickets.id = previous.id
+            RETURNING
+                previous.id AS "id!",
+                previous.title AS "title!",
+                previous.description AS "description!",
+                previous.status AS "status!",
+                previous.assignee,
+                previous.reporter,
+                previous.priority AS "priority!",
+                previous.due_date,
+                previous.version AS "version!",
+                previous.created_at AS "created_at!",
+                previous.updated_at AS "updated_at!",
+                previous.closed_at,
+                previous.labels AS "labels!"
+            "#,
+            &ids,
+            &versions,
+            &titles,
+            &descriptions,
+            &statuses as _,
+            &assignees as _,
+            &priorities as _,
+            &due_dates as _,
+            &updated_ats,
+            &closed_ats as _,
+        )
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        if previous_rows.len() != tickets.len() {
             return Err(DomainError::ConcurrentModification);
         }
+
+        let mut previous_rows = previous_rows
+            .into_iter()
+            .map(|row| (row.id, row))
+            .collect::<HashMap<_, _>>();
+        for mut ticket in tickets {
+            let previous = previous_rows
+                .remove(&ticket.id().value())
+                .ok_or(DomainError::ConcurrentModification)?;
+            record_save(&mut tx, previous, &mut ticket, self.audit).await?;
+        }
         Ok(())
     }
 }
 
+/// Writes what goes with an updated ticket row: its labels, the audit entry
+/// against the row as it was before, and the events for the outbox.
+async fn record_save(
+    tx: &mut Transaction<'static, Postgres>,
+    previous: TicketRow,
+    ticket: &mut Ticket,
+    audit: &AuditContext,
+) -> Result<()> {
+    if previous.labels != ticket.labels() {
+        sync_labels(tx, ticket).await?;
+    }
+    let current = TicketSnapshot {
+        version: previous.version + 1,
+        ..TicketSnapshot::from(&*ticket)
+    };
+    let previous = TicketSnapshot::from(previous);
+    sqlx_ticket_audit::append(tx, ticket.id(), Some(&previous), &current, audit).await?;
+    append_events(tx, ticket).await
+}
+
+/// Makes the `ticket_labels` rows of `ticket` match its labels, creating
+/// labels no ticket has used before.
+async fn sync_labels(tx: &mut Transaction<'static, Postgres>, ticket: &Ticket) -> Result<()> {
+    let labels = ticket.labels();
+    sqlx::query!(
+        "INSERT INTO labels (name) SELECT unnest($1::text[]) ON CONFLICT (name) DO NOTHING",
+        &labels,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+    sqlx::query!(
+        r#"
+        DELETE FROM ticket_labels
+        WHERE ticket_id = $1
+          AND label_id NOT IN (SELECT id FROM labels WHERE name = ANY($2))
+        "#,
+        ticket.id().value(),
+        &labels,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+    sqlx::query!(
+        r#"
+        INSERT INTO ticket_labels (ticket_id, label_id)
+        SELECT $1, id FROM labels WHERE name = ANY($2)
+        ON CONFLICT DO NOTHING
+        "#,
+        ticket.id().value(),
+        &labels,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(repository_error)?;
+    Ok(())
+}
+
+/// Distinct label names, as the all-of filter compares against their count.
+pub(crate) fn label_names(labels: &[TicketLabel]) -> Vec<String> {
+    labels
+        .iter()
+        .map(|l| l.as_ref().to_owned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Moves the events recorded by `ticket` into the outbox of this transaction.
+async fn append_events(tx: &mut Transaction<'static, Postgres>, ticket: &mut Ticket) -> Result<()> {
+    let messages = ticket
+        .take_events()
+        .iter()
+        .map(|event| OutboxMessage::from_ticket_event(ticket.id(), event))
+        .collect::<Vec<_>>();
+    sqlx_outbox_store::append(tx, &messages).await
+}
+
+/// Postgres reports serialization failures (40001) and deadlocks (40P01) for
+/// transactions that may succeed when run again.
+fn is_transaction_conflict(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .and_then(|db| db.code())
+        .is_some_and(|code| code == "40001" || code == "40P01")
+}
+
+pub(crate) fn repository_error(e: sqlx::Error) -> DomainError {
+    if is_transaction_conflict(&e) {
+        DomainError::TransactionConflict(e.to_string())
+    } else {
+        DomainError::RepositoryError(e.to_string())
+    }
+}
+
+fn infrastructure_error(e: sqlx::Error) -> DomainError {
+    if is_transaction_conflict(&e) {
+        DomainError::TransactionConflict(e.to_string())
+    } else {
+        DomainError::Infrastructure(e.into())
+    }
+}
+
+/// Escapes `LIKE` wildcards so user input is matched literally.
+pub(crate) fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 struct TicketRow {
     id: Uuid,
     title: String,
     description: String,
     status: String,
     assignee: Option<Uuid>,
+    reporter: Option<String>,
+    priority: String,
+    due_date: Option<NaiveDate>,
     version: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    closed_at: Option<DateTime<Utc>>,
+    labels: Vec<String>,
+}
+
+impl TryFrom<TicketRow> for Ticket {
+    type Error = DomainError;
+
+    fn try_from(row: TicketRow) -> Result<Self> {
+        Ok(Ticket::reconstruct(
+            TicketId::from(row.id),
+            row.title,
+            row.description,
+            ticket_status_codec::decode(&row.status, row.assignee)?,
+            row.assignee.map(UserId::from),
+            row.reporter,
+            row.priority.parse()?,
+            row.due_date,
+            row.labels,
+            row.version,
+            TicketTimestamps {
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                closed_at: row.closed_at,
+            },
+        ))
+    }
+}
+
+impl From<TicketRow> for TicketSnapshot {
+    fn from(row: TicketRow) -> Self {
+        Self {
+            title: row.title,
+            description: row.description,
+            status: row.status,
+            assignee: row.assignee,
+            // The column is constrained to the known priorities
+            priority: row.priority.parse().unwrap_or_default(),
+            due_date: row.due_date,
+            labels: row.labels,
+            version: row.version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use crate::domain::tickets::ticket_priority::TicketPriority;
+
+    /// A transaction on the migrated database in `DATABASE_URL`, rolled back
+    /// when dropped, or `None` to skip the test when no database is set.
+    async fn transaction() -> Option<Mutex<Transaction<'static, Postgres>>> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        Some(Mutex::new(pool.begin().await.unwrap()))
+    }
+
+    /// A word no other ticket contains, so searches see only this test's.
+    fn unique_word() -> String {
+        Uuid::new_v4()
+            .simple()
+            .to_string()
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => char::from(b'g' + digit as u8),
+                None => c,
+            })
+            .collect()
+    }
+
+    async fn search(repo: &SqlxTicketRepository<'_>, text: String) -> Vec<TicketSearchHit> {
+        let search = TicketSearch::new(text, 10).unwrap();
+        repo.search(&search).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn search_ranks_title_matches_first_and_falls_back_to_substrings() {
+        let Some(tx) = transaction().await else {
+            return;
+        };
+        let audit = AuditContext::default();
+        let mut repo = SqlxTicketRepository {
+            tx: &tx,
+            audit: &audit,
+        };
+        let word = unique_word();
+        let reporter = Actor::new("tester", vec![]);
+        let mut insert = async |title: String, description: String| {
+            let ticket = Ticket::new(
+                title,
+                description,
+                None,
+                &reporter,
+                TicketPriority::default(),
+                None,
+                Utc::now(),
+            )
+            .unwrap();
+            let id = ticket.id();
+            repo.insert(ticket).await.unwrap();
+            id
+        };
+        let in_title = insert(
+            format!("{word} printer jammed"),
+            "<b>Paper</b> stuck in tray 2".to_owned(),
+        )
+        .await;
+        let in_description = insert(
+            "Office supplies".to_owned(),
+            format!("The {word} printer is jammed"),
+        )
+        .await;
+        let noisy = insert(format!("{word} scanner <noise>"), "Rattles".to_owned()).await;
+        let hits = search(&repo, format!("{word} printers")).await;
+        let ids = hits.iter().map(|h| h.ticket.id()).collect::<Vec<_>>();
+        assert_eq!(ids, [in_title, in_description]);
+        assert!(hits[0].rank > hits[1].rank);
+        assert_eq!(
+            hits[0].title,
+            format!("<mark>{word}</mark> <mark>printer</mark> jammed")
+        );
+        assert!(
+            hits[0]
+                .snippet
+                .starts_with("&lt;b&gt;Paper&lt;/b&gt; stuck"),
+            "{}",
+            hits[0].snippet
+        );
+
+        // "oise" is no word of the text search, only a substring
+        let hits = search(&repo, format!("{word} oise")).await;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].ticket.id(), noisy);
+        assert!(hits[0].rank > 0.0);
+        assert_eq!(
+            hits[0].title,
+            format!("<mark>{word}</mark> scanner &lt;n<mark>oise</mark>&gt;")
+        );
+    }
 }
@@ -0,0 +1,84 @@
+use crate::domain::error::Result;
+use crate::domain::users::repository::UserRepository;
+use crate::domain::users::user::User;
+use crate::domain::users::user_error::UserError;
+use crate::domain::users::user_id::UserId;
+use crate::infrastructure::repository::sqlx_ticket_repository::repository_error;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+pub struct SqlxUserRepository<'a> {
+    tx: &'a Mutex<Transaction<'static, Postgres>>,
+}
+
+impl<'a> SqlxUserRepository<'a> {
+    pub(super) fn new(tx: &'a Mutex<Transaction<'static, Postgres>>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl<'a> UserRepository for SqlxUserRepository<'a> {
+    async fn find_by_id(&self, id: UserId) -> Result<User> {
+        let mut tx = self.tx.lock().await;
+        let row = sqlx::query_as!(
+            UserRow,
+            "SELECT id, name, active, created_at FROM users WHERE id = $1",
+            id.value(),
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(repository_error)?
+        .ok_or(UserError::NotFound)?;
+
+        Ok(row.into())
+    }
+
+    async fn insert(&mut self, user: User) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        sqlx::query!(
+            "INSERT INTO users (id, name, active, created_at) VALUES ($1, $2, $3, $4)",
+            user.id().value(),
+            user.name(),
+            user.is_active(),
+            user.created_at(),
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        Ok(())
+    }
+
+    async fn save(&mut self, user: User) -> Result<()> {
+        let mut tx = self.tx.lock().await;
+        let result = sqlx::query!(
+            "UPDATE users SET name = $1, active = $2 WHERE id = $3",
+            user.name(),
+            user.is_active(),
+            user.id().value(),
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(repository_error)?;
+        if result.rows_affected() == 0 {
+            return Err(UserError::NotFound.into());
+        }
+        Ok(())
+    }
+}
+
+struct UserRow {
+    id: Uuid,
+    name: String,
+    active: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<UserRow> for User {
+    fn from(row: UserRow) -> Self {
+        User::reconstruct(UserId::from(row.id), row.name, row.active, row.created_at)
+    }
+}
@@ -0,0 +1,94 @@
+//! Persistence mapping for [`TicketStatus`].
+//!
+//! The `tickets` table stores the status name in `status` and the user in
+//! `assignee`, so statuses carrying a user are rebuilt from both columns.
+
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_status::{TicketStatus, TicketStatusKind};
+use crate::domain::users::user_id::UserId;
+use uuid::Uuid;
+
+/// Returns the `status` column value and the user held by the status.
+pub fn encode(status: &TicketStatus) -> (&'static str, Option<Uuid>) {
+    (status.kind().as_str(), status.user_id().map(|u| u.value()))
+}
+
+/// Rebuilds a status from the `status` and `assignee` columns.
+pub fn decode(status: &str, assignee: Option<Uuid>) -> Result<TicketStatus, TicketError> {
+    let assignee = assignee.map(UserId::from);
+    let status = match status.parse::<TicketStatusKind>()? {
+        TicketStatusKind::Open => TicketStatus::Open,
+        TicketStatusKind::Assigned => TicketStatus::Assigned {
+            user_id: assignee.ok_or(TicketError::InvalidStatus)?,
+        },
+        TicketStatusKind::InProgress => TicketStatus::InProgress {
+            user_id: assignee.ok_or(TicketError::InvalidStatus)?,
+        },
+        TicketStatusKind::Resolved => TicketStatus::Resolved,
+        TicketStatusKind::Closed => TicketStatus::Closed,
+    };
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn any_status() -> impl Strategy<Value = TicketStatus> {
+        let user_id = any::<u128>().prop_map(|n| UserId::from(Uuid::from_u128(n)));
+        prop_oneof![
+            Just(TicketStatus::Open),
+            user_id
+                .clone()
+                .prop_map(|user_id| TicketStatus::Assigned { user_id }),
+            user_id.prop_map(|user_id| TicketStatus::InProgress { user_id }),
+            Just(TicketStatus::Resolved),
+            Just(TicketStatus::Closed),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn decode_restores_encoded_status(status in any_status()) {
+            let (name, assignee) = encode(&status);
+            prop_assert_eq!(decode(name, assignee).unwrap(), status);
+        }
+
+        #[test]
+        fn decode_ignores_previous_assignee_of_unassigned_status(
+            status in any_status(),
+            previous in any::<u128>().prop_map(Uuid::from_u128),
+        ) {
+            prop_assume!(status.user_id().is_none());
+            let (name, _) = encode(&status);
+            prop_assert_eq!(decode(name, Some(previous)).unwrap(), status);
+        }
+
+        #[test]
+        fn decode_accepts_any_case(status in any_status()) {
+            let (name, assignee) = encode(&status);
+            prop_assert_eq!(decode(&name.to_uppercase(), assignee).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_status_with_user_but_no_assignee() {
+        assert!(matches!(
+            decode("assigned", None),
+            Err(TicketError::InvalidStatus)
+        ));
+        assert!(matches!(
+            decode("in_progress", None),
+            Err(TicketError::InvalidStatus)
+        ));
+    }
+
+    #[test]
+    fn decode_rejects_unknown_status() {
+        assert!(matches!(
+            decode("pending", None),
+            Err(TicketError::InvalidStatus)
+        ));
+    }
+}
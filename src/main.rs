@@ -1,6 +1,16 @@
+use crate::domain::clock::{Clock, SystemClock};
+use crate::domain::tickets::repository::UowFactory;
+use crate::infrastructure::outbox::event_sink::{FileEventSink, LogEventSink, WebhookEventSink};
+use crate::infrastructure::outbox::outbox_relay::OutboxRelay;
+use crate::infrastructure::outbox::sqlx_outbox_store::SqlxOutboxStore;
+use crate::infrastructure::outbox::{EventSink, OutboxStore};
+use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+use crate::infrastructure::repository::sqlx_event_store_ticket_repository;
 use crate::infrastructure::repository::sqlx_ticket_repository::SqlxUowFactory;
+use crate::presentation::auth::{
+    ApiKeyAuthenticator, Authenticator, JwtAuthenticator, NoAuthentication,
+};
 use crate::presentation::{http, AppState};
-use axum::Router;
 use opentelemetry::trace::TracerProvider;
 use opentelemetry_otlp::WithExportConfig;
 use sqlx::PgPool;
@@ -15,12 +25,12 @@ mod application;
 mod domain;
 mod infrastructure;
 mod presentation;
+#[cfg(test)]
+mod test_fixtures;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    dotenv::dotenv().expect("Failed to load .env file");
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let pool = PgPool::connect(&database_url).await?;
+    dotenv::dotenv().ok();
 
     // OTLP Exporter setting
     let endpoint = "http://localhost:4317";
@@ -60,12 +70,109 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     log::info!("Application started successfully");
 
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let storage = arg_value("--storage=").unwrap_or_else(|| "postgres".to_owned());
+    let (uow_factory, outbox_store): (Arc<dyn UowFactory>, Arc<dyn OutboxStore>) = match storage
+        .as_str()
+    {
+        "postgres" | "event-store" => {
+            let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+            let pool = PgPool::connect(&database_url).await?;
+            if std::env::args().any(|arg| arg == "--migrate") {
+                sqlx::migrate!().run(&pool).await?;
+                log::info!("Database migrations applied");
+            }
+            let uow_factory = if storage == "event-store" {
+                let projected = sqlx_event_store_ticket_repository::project_missing(&pool).await?;
+                if projected > 0 {
+                    log::info!("Projected {projected} ticket event streams");
+                }
+                SqlxUowFactory::with_event_store(pool.clone())
+            } else {
+                SqlxUowFactory::new(pool.clone())
+            };
+            (
+                Arc::new(uow_factory),
+                Arc::new(SqlxOutboxStore::new(pool)),
+            )
+        }
+        "memory" => {
+            log::warn!("Using in-memory storage; data is lost on shutdown");
+            let factory = InMemoryUowFactory::new(clock.clone());
+            (Arc::new(factory.clone()), Arc::new(factory))
+        }
+        other => return Err(format!("Unknown storage: {other}").into()),
+    };
+
+    let outbox_sink = arg_value("--outbox-sink=").unwrap_or_else(|| "log".to_owned());
+    let sink: Arc<dyn EventSink> = if outbox_sink == "log" {
+        Arc::new(LogEventSink)
+    } else if let Some(path) = outbox_sink.strip_prefix("file:") {
+        Arc::new(FileEventSink::new(path))
+    } else if let Some(url) = outbox_sink.strip_prefix("webhook:") {
+        Arc::new(WebhookEventSink::new(url))
+    } else {
+        return Err(format!("Unknown outbox sink: {outbox_sink}").into());
+    };
+    tokio::spawn(OutboxRelay::new(outbox_store, sink).run());
+
+    let insecure_no_auth = std::env::args().any(|arg| arg == "--insecure-no-auth");
+    let authenticator: Arc<dyn Authenticator> = match (arg_value("--auth="), insecure_no_auth) {
+        (Some(_), true) => {
+            return Err("--auth and --insecure-no-auth cannot be used together".into());
+        }
+        (None, true) => {
+            log::warn!("Authentication is disabled; every request acts as an anonymous admin");
+            Arc::new(NoAuthentication)
+        }
+        (None, false) => {
+            return Err(
+                "Choose an authentication mode with --auth=jwt-hs256|jwt-rs256|api-key, \
+                 or pass --insecure-no-auth for local development"
+                    .into(),
+            );
+        }
+        (Some(auth), false) => match auth.as_str() {
+            "jwt-hs256" | "jwt-rs256" => {
+                let jwt = if auth == "jwt-hs256" {
+                    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+                    JwtAuthenticator::hs256(secret.as_bytes())
+                } else {
+                    let path = std::env::var("JWT_PUBLIC_KEY_FILE")
+                        .expect("JWT_PUBLIC_KEY_FILE must be set");
+                    JwtAuthenticator::rs256(&std::fs::read(path)?)?
+                };
+                let jwt = match std::env::var("JWT_ISSUER") {
+                    Ok(issuer) => jwt.with_issuer(&issuer),
+                    Err(_) => jwt,
+                };
+                let jwt = match std::env::var("JWT_AUDIENCE") {
+                    Ok(audience) => jwt.with_audience(&audience),
+                    Err(_) => jwt,
+                };
+                Arc::new(jwt)
+            }
+            "api-key" => {
+                let keys = std::env::var("API_KEYS").expect("API_KEYS must be set");
+                Arc::new(ApiKeyAuthenticator::parse(&keys)?)
+            }
+            other => return Err(format!("Unknown auth mode: {other}").into()),
+        },
+    };
+
     let service = AppState {
-        uow_factory: Arc::new(SqlxUowFactory::new(pool)),
+        uow_factory,
+        clock,
+        authenticator,
     };
-    let app: Router = http::router().with_state(service);
+    let app = http::router(service);
 
     let listener = TcpListener::bind("0.0.0.0:3001").await?;
     axum::serve(listener, app.into_make_service()).await?;
     Ok(())
 }
+
+/// Returns the value of the first `--name=value` style argument with `prefix`.
+fn arg_value(prefix: &str) -> Option<String> {
+    std::env::args().find_map(|arg| arg.strip_prefix(prefix).map(str::to_owned))
+}
@@ -1,24 +1,497 @@
+use crate::domain::comments::comment_body::CommentBodyError;
+use crate::domain::comments::comment_error::CommentError;
 use crate::domain::error::DomainError;
-use axum::http::StatusCode;
+use crate::domain::idempotency::idempotency_error::IdempotencyError;
+use crate::domain::idempotency::idempotency_key::IdempotencyKeyError;
+use crate::domain::tickets::ticket_description::TicketDescriptionError;
+use crate::domain::tickets::ticket_due_date::TicketDueDateError;
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_label::TicketLabelError;
+use crate::domain::tickets::ticket_title::TicketTitleError;
+use crate::domain::users::user_error::UserError;
+use crate::domain::users::user_name::UserNameError;
+use crate::presentation::trace_context;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
-use serde_json::json;
+use serde::Serialize;
 
-impl IntoResponse for DomainError {
+const PROBLEM_TYPE_PREFIX: &str = "urn:learn-rust:problem:";
+
+/// An RFC 7807 `application/problem+json` error response.
+///
+/// `code` is stable across releases and is what clients should match on;
+/// `title` and `detail` are meant for humans and may change.
+#[derive(Debug)]
+pub struct Problem {
+    status: StatusCode,
+    code: &'static str,
+    title: &'static str,
+    detail: Option<String>,
+    errors: Vec<FieldError>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+struct ProblemBody<'a> {
+    #[serde(rename = "type")]
+    type_uri: String,
+    title: &'a str,
+    status: u16,
+    code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    errors: &'a [FieldError],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<String>,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode, code: &'static str, title: &'static str) -> Self {
+        Self {
+            status,
+            code,
+            title,
+            detail: None,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_field_error(mut self, error: FieldError) -> Self {
+        self.errors.push(error);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn internal_error() -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal-error",
+            "Internal server error",
+        )
+        .with_detail("An unexpected error occurred. Please report the trace id.")
+    }
+
+    fn validation_failed(error: FieldError) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "validation-failed",
+            "Request validation failed",
+        )
+        .with_field_error(error)
+    }
+}
+
+impl Problem {
+    fn body(&self) -> ProblemBody<'_> {
+        ProblemBody {
+            type_uri: format!("{PROBLEM_TYPE_PREFIX}{}", self.code),
+            title: self.title,
+            status: self.status.as_u16(),
+            code: self.code,
+            detail: self.detail.as_deref(),
+            errors: &self.errors,
+            trace_id: trace_context::current_trace_id(),
+        }
+    }
+}
+
+impl IntoResponse for Problem {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            DomainError::Ticket(ticket_error) => {
-                (StatusCode::BAD_REQUEST, ticket_error.to_string())
-            }
+        let content_type = [(header::CONTENT_TYPE, "application/problem+json")];
+        (self.status, content_type, Json(self.body())).into_response()
+    }
+}
+
+/// Serializes as the response body, for problems embedded in other responses.
+impl Serialize for Problem {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.body().serialize(serializer)
+    }
+}
+
+impl From<DomainError> for Problem {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::Ticket(ticket_error) => ticket_error.into(),
+            DomainError::Comment(comment_error) => comment_error.into(),
+            DomainError::User(user_error) => user_error.into(),
+            DomainError::Idempotency(idempotency_error) => idempotency_error.into(),
             DomainError::RepositoryError(repository_error) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, repository_error)
+                tracing::error!(error = %repository_error, "Repository error");
+                Problem::internal_error()
+            }
+            DomainError::ConcurrentModification => Problem::new(
+                StatusCode::CONFLICT,
+                "concurrent-modification",
+                "Concurrent modification",
+            )
+            .with_detail("The ticket was modified by another request. Please retry."),
+            DomainError::TransactionConflict(conflict) => {
+                tracing::warn!(error = %conflict, "Transaction conflict");
+                Problem::new(
+                    StatusCode::CONFLICT,
+                    "transaction-conflict",
+                    "Transaction conflict",
+                )
+                .with_detail("The request conflicted with a concurrent request. Please retry.")
+            }
+            DomainError::VersionMismatch { expected } => Problem::new(
+                StatusCode::PRECONDITION_FAILED,
+                "version-mismatch",
+                "Version mismatch",
+            )
+            .with_detail(format!(
+                "The ticket is no longer at version {expected}. Fetch it again and retry."
+            )),
+            DomainError::Infrastructure(infrastructure_error) => {
+                tracing::error!(error = %infrastructure_error, "Infrastructure error");
+                Problem::internal_error()
+            }
+            DomainError::InvalidTicketId => Problem::new(
+                StatusCode::BAD_REQUEST,
+                "invalid-ticket-id",
+                "Invalid ticket id",
+            ),
+            DomainError::Forbidden(reason) => {
+                Problem::new(StatusCode::FORBIDDEN, "forbidden", "Forbidden").with_detail(reason)
+            }
+        }
+    }
+}
+
+impl From<TicketError> for Problem {
+    fn from(error: TicketError) -> Self {
+        match error {
+            TicketError::NotFound => Problem::new(
+                StatusCode::NOT_FOUND,
+                "ticket-not-found",
+                "Ticket not found",
+            ),
+            TicketError::EmptySearch => Problem::validation_failed(FieldError {
+                field: "q",
+                code: "empty",
+                message: error.to_string(),
+            }),
+            TicketError::InvalidCursor => Problem::validation_failed(FieldError {
+                field: "cursor",
+                code: "invalid",
+                message: error.to_string(),
+            }),
+            TicketError::InvalidStatus => Problem::new(
+                StatusCode::BAD_REQUEST,
+                "invalid-status",
+                "Invalid ticket status",
+            ),
+            TicketError::InvalidPriority => Problem::validation_failed(FieldError {
+                field: "priority",
+                code: "invalid",
+                message: error.to_string(),
+            }),
+            TicketError::DueDateInPast => Problem::validation_failed(FieldError {
+                field: "due_date",
+                code: "in_past",
+                message: error.to_string(),
+            }),
+            TicketError::TooManyLabels { .. } => Problem::validation_failed(FieldError {
+                field: "labels",
+                code: "too_many",
+                message: error.to_string(),
+            }),
+            TicketError::UnknownAssignee => Problem::validation_failed(FieldError {
+                field: "user_id",
+                code: "unknown",
+                message: error.to_string(),
+            }),
+            TicketError::InactiveAssignee => Problem::validation_failed(FieldError {
+                field: "user_id",
+                code: "inactive",
+                message: error.to_string(),
+            }),
+            TicketError::InvalidTransition { .. } => Problem::new(
+                StatusCode::CONFLICT,
+                "invalid-transition",
+                "Invalid status transition",
+            )
+            .with_detail(error.to_string()),
+            TicketError::EmptyTitle => Problem::validation_failed(FieldError {
+                field: "title",
+                code: "empty",
+                message: error.to_string(),
+            }),
+            TicketError::TooLongTitle => Problem::validation_failed(FieldError {
+                field: "title",
+                code: "too_long",
+                message: error.to_string(),
+            }),
+            TicketError::EmptyDescription => Problem::validation_failed(FieldError {
+                field: "description",
+                code: "empty",
+                message: error.to_string(),
+            }),
+            TicketError::TooLongDescription => Problem::validation_failed(FieldError {
+                field: "description",
+                code: "too_long",
+                message: error.to_string(),
+            }),
+            TicketError::TicketTitleError(title_error) => {
+                let code = match title_error {
+                    TicketTitleError::NotEmptyViolated => "empty",
+                    TicketTitleError::LenCharMaxViolated => "too_long",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "title",
+                    code,
+                    message: title_error.to_string(),
+                })
+            }
+            TicketError::TicketDescriptionError(description_error) => {
+                let code = match description_error {
+                    TicketDescriptionError::NotEmptyViolated => "empty",
+                    TicketDescriptionError::LenCharMaxViolated => "too_long",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "description",
+                    code,
+                    message: description_error.to_string(),
+                })
+            }
+            TicketError::TicketLabelError(label_error) => {
+                let code = match label_error {
+                    TicketLabelError::NotEmptyViolated => "empty",
+                    TicketLabelError::LenCharMaxViolated => "too_long",
+                    TicketLabelError::PredicateViolated => "invalid",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "label",
+                    code,
+                    message: label_error.to_string(),
+                })
+            }
+            TicketError::TicketDueDateError(due_date_error) => {
+                let code = match due_date_error {
+                    TicketDueDateError::PredicateViolated => "out_of_range",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "due_date",
+                    code,
+                    message: due_date_error.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl From<CommentError> for Problem {
+    fn from(error: CommentError) -> Self {
+        match error {
+            CommentError::NotFound => Problem::new(
+                StatusCode::NOT_FOUND,
+                "comment-not-found",
+                "Comment not found",
+            ),
+            CommentError::TicketClosed => {
+                Problem::new(StatusCode::CONFLICT, "ticket-closed", "Ticket is closed")
+                    .with_detail(error.to_string())
+            }
+            CommentError::CommentBodyError(body_error) => {
+                let code = match body_error {
+                    CommentBodyError::NotEmptyViolated => "empty",
+                    CommentBodyError::LenCharMaxViolated => "too_long",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "body",
+                    code,
+                    message: body_error.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl From<UserError> for Problem {
+    fn from(error: UserError) -> Self {
+        match error {
+            UserError::NotFound => {
+                Problem::new(StatusCode::NOT_FOUND, "user-not-found", "User not found")
+            }
+            UserError::UserNameError(name_error) => {
+                let code = match name_error {
+                    UserNameError::NotEmptyViolated => "empty",
+                    UserNameError::LenCharMaxViolated => "too_long",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "name",
+                    code,
+                    message: name_error.to_string(),
+                })
+            }
+        }
+    }
+}
+
+impl From<IdempotencyError> for Problem {
+    fn from(error: IdempotencyError) -> Self {
+        match error {
+            IdempotencyError::KeyReused => Problem::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "idempotency-key-reused",
+                "Idempotency key reused",
+            )
+            .with_detail(error.to_string()),
+            IdempotencyError::IdempotencyKeyError(key_error) => {
+                let code = match key_error {
+                    IdempotencyKeyError::NotEmptyViolated => "empty",
+                    IdempotencyKeyError::LenCharMaxViolated => "too_long",
+                    IdempotencyKeyError::PredicateViolated => "invalid",
+                };
+                Problem::validation_failed(FieldError {
+                    field: "Idempotency-Key",
+                    code,
+                    message: key_error.to_string(),
+                })
             }
-            DomainError::ConcurrentModification => (StatusCode::CONFLICT, self.to_string()),
-            DomainError::Infrastructure(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            DomainError::InvalidTicketId => (StatusCode::BAD_REQUEST, self.to_string()),
+        }
+    }
+}
+
+impl From<JsonRejection> for Problem {
+    fn from(rejection: JsonRejection) -> Self {
+        let (code, title) = match rejection {
+            JsonRejection::MissingJsonContentType(_) => {
+                ("unsupported-media-type", "Unsupported media type")
+            }
+            _ => ("invalid-body", "Invalid request body"),
         };
+        Problem::new(rejection.status(), code, title).with_detail(rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for Problem {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(_) => Problem::new(
+                rejection.status(),
+                "invalid-path-parameter",
+                "Invalid path parameter",
+            )
+            .with_detail(rejection.body_text()),
+            _ => {
+                tracing::error!(error = %rejection.body_text(), "Path extraction failed");
+                Problem::internal_error()
+            }
+        }
+    }
+}
+
+impl From<QueryRejection> for Problem {
+    fn from(rejection: QueryRejection) -> Self {
+        Problem::new(rejection.status(), "invalid-query", "Invalid query string")
+            .with_detail(rejection.body_text())
+    }
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        Problem::from(self).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    async fn respond(error: DomainError) -> (StatusCode, String, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn problems_carry_their_type_status_and_code() {
+        let (status, content_type, body) = respond(TicketError::NotFound.into()).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(content_type, "application/problem+json");
+        assert_eq!(
+            body,
+            json!({
+                "type": "urn:learn-rust:problem:ticket-not-found",
+                "title": "Ticket not found",
+                "status": 404,
+                "code": "ticket-not-found",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn validation_problems_name_the_invalid_field() {
+        let (status, _, body) =
+            respond(TicketError::TicketTitleError(TicketTitleError::LenCharMaxViolated).into())
+                .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["type"], "urn:learn-rust:problem:validation-failed");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(body["errors"][0]["field"], "title");
+        assert_eq!(body["errors"][0]["code"], "too_long");
+    }
+
+    #[tokio::test]
+    async fn storage_failures_are_reported_without_their_details() {
+        let secret = "password authentication failed for user \"postgres\"";
+        let errors = [
+            DomainError::RepositoryError(secret.to_owned()),
+            DomainError::Infrastructure(secret.into()),
+        ];
+
+        for error in errors {
+            let (status, _, body) = respond(error).await;
+
+            assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(body["code"], "internal-error");
+            assert!(!body.to_string().contains("postgres"), "{body}");
+        }
+    }
+
+    #[test]
+    fn embedded_problems_serialize_like_responses() {
+        let problem = Problem::from(DomainError::VersionMismatch { expected: 3 });
+
+        let body = serde_json::to_value(&problem).unwrap();
 
-        let body = Json(json!({ "error": error_message }));
-        (status, body).into_response()
+        assert_eq!(problem.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(body["type"], "urn:learn-rust:problem:version-mismatch");
+        assert_eq!(body["status"], 412);
+        assert!(body["detail"].as_str().unwrap().contains("version 3"));
     }
 }
@@ -0,0 +1,308 @@
+use crate::domain::actor::Actor;
+use crate::presentation::app_error::Problem;
+use crate::presentation::AppState;
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::request::Parts;
+use axum::http::{header, HeaderMap, HeaderName, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+
+pub const API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Establishes who sent a request from its headers.
+pub trait Authenticator: Send + Sync {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Actor, AuthError>;
+
+    /// Value of the `WWW-Authenticate` header sent with a 401.
+    fn challenge(&self) -> &'static str;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("No credentials were sent")]
+    MissingCredentials,
+    #[error("The bearer token is invalid: {0}")]
+    InvalidToken(#[from] jsonwebtoken::errors::Error),
+    #[error("The API key is not known")]
+    UnknownApiKey,
+}
+
+/// Rejects requests that do not authenticate, and makes the `Actor` of the
+/// others available to handlers.
+pub async fn authenticate(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match state.authenticator.authenticate(request.headers()) {
+        Ok(actor) => {
+            tracing::Span::current().record("actor", &actor.subject);
+            request.extensions_mut().insert(actor);
+            next.run(request).await
+        }
+        Err(e) => {
+            tracing::info!(error = %e, "Request not authenticated");
+            let challenge = [(header::WWW_AUTHENTICATE, state.authenticator.challenge())];
+            let problem = Problem::new(
+                StatusCode::UNAUTHORIZED,
+                "unauthenticated",
+                "Authentication required",
+            )
+            .with_detail(e.to_string());
+            (challenge, problem).into_response()
+        }
+    }
+}
+
+/// Lets handlers take the actor established by `authenticate` as an argument.
+impl<S: Send + Sync> FromRequestParts<S> for Actor {
+    type Rejection = Problem;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Actor>().cloned().ok_or_else(|| {
+            Problem::new(
+                StatusCode::UNAUTHORIZED,
+                "unauthenticated",
+                "Authentication required",
+            )
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+/// Accepts `Authorization: Bearer` JWTs signed with a locally configured key.
+///
+/// The `sub` claim becomes the actor and the optional `roles` claim its roles.
+pub struct JwtAuthenticator {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+impl JwtAuthenticator {
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self::new(DecodingKey::from_secret(secret), Algorithm::HS256)
+    }
+
+    pub fn rs256(public_key_pem: &[u8]) -> Result<Self, jsonwebtoken::errors::Error> {
+        Ok(Self::new(
+            DecodingKey::from_rsa_pem(public_key_pem)?,
+            Algorithm::RS256,
+        ))
+    }
+
+    fn new(key: DecodingKey, algorithm: Algorithm) -> Self {
+        let mut validation = Validation::new(algorithm);
+        validation.validate_aud = false;
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        Self { key, validation }
+    }
+
+    /// Only accepts tokens whose `iss` claim is `issuer`.
+    pub fn with_issuer(mut self, issuer: &str) -> Self {
+        self.validation.set_issuer(&[issuer]);
+        self.validation
+            .required_spec_claims
+            .insert("iss".to_owned());
+        self
+    }
+
+    /// Only accepts tokens whose `aud` claim is or contains `audience`.
+    pub fn with_audience(mut self, audience: &str) -> Self {
+        self.validation.set_audience(&[audience]);
+        self.validation.validate_aud = true;
+        self.validation
+            .required_spec_claims
+            .insert("aud".to_owned());
+        self
+    }
+}
+
+impl Authenticator for JwtAuthenticator {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Actor, AuthError> {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::MissingCredentials)?;
+        let claims =
+            jsonwebtoken::decode::<Claims>(token.trim(), &self.key, &self.validation)?.claims;
+        Ok(Actor::new(claims.sub, claims.roles))
+    }
+
+    fn challenge(&self) -> &'static str {
+        "Bearer"
+    }
+}
+
+/// Accepts static keys sent in `X-Api-Key`, for scripts and integrations.
+///
+/// Key holders are trusted services and act with the admin role.
+pub struct ApiKeyAuthenticator {
+    /// Pairs of actor name and key.
+    keys: Vec<(String, String)>,
+}
+
+impl ApiKeyAuthenticator {
+    /// Parses keys configured as `name=key,name=key`.
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let keys = config
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| match entry.trim().split_once('=') {
+                Some((name, key)) if !name.is_empty() && !key.is_empty() => {
+                    Ok((name.to_owned(), key.to_owned()))
+                }
+                _ => Err(format!(
+                    "Invalid API key entry: expected name=key, got {entry:?}"
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err("No API keys are configured".to_owned());
+        }
+        Ok(Self { keys })
+    }
+}
+
+impl Authenticator for ApiKeyAuthenticator {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Actor, AuthError> {
+        let sent = headers
+            .get(API_KEY)
+            .ok_or(AuthError::MissingCredentials)?
+            .as_bytes();
+        // Compare every key so the time taken does not tell which one matched
+        let mut actor = None;
+        for (name, key) in &self.keys {
+            if constant_time_eq(sent, key.as_bytes()) {
+                actor = Some(name);
+            }
+        }
+        actor
+            .map(|name| Actor::new(name.clone(), vec![Actor::ADMIN.to_owned()]))
+            .ok_or(AuthError::UnknownApiKey)
+    }
+
+    fn challenge(&self) -> &'static str {
+        "ApiKey"
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Treats every request as sent by the same anonymous admin, for local
+/// development only. `main` never picks it unless `--insecure-no-auth` is
+/// passed.
+pub struct NoAuthentication;
+
+impl Authenticator for NoAuthentication {
+    fn authenticate(&self, _headers: &HeaderMap) -> Result<Actor, AuthError> {
+        Ok(Actor::new("anonymous", vec![Actor::ADMIN.to_owned()]))
+    }
+
+    fn challenge(&self) -> &'static str {
+        "Bearer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use jsonwebtoken::{EncodingKey, Header};
+
+    fn bearer(secret: &[u8], claims: serde_json::Value) -> HeaderMap {
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap();
+        let mut headers = HeaderMap::new();
+        let value = HeaderValue::from_str(&format!("Bearer {token}")).unwrap();
+        headers.insert(header::AUTHORIZATION, value);
+        headers
+    }
+
+    #[test]
+    fn jwt_subject_and_roles_become_the_actor_only_when_signed_with_the_key() {
+        let jwt = JwtAuthenticator::hs256(b"secret");
+        let exp = chrono::Utc::now().timestamp() + 60;
+        let claims = serde_json::json!({ "sub": "alice", "roles": ["admin"], "exp": exp });
+
+        let actor = jwt
+            .authenticate(&bearer(b"secret", claims.clone()))
+            .unwrap();
+        let forged = jwt.authenticate(&bearer(b"other", claims));
+        let expired = jwt.authenticate(&bearer(
+            b"secret",
+            serde_json::json!({ "sub": "alice", "exp": exp - 3600 }),
+        ));
+
+        assert_eq!(actor, Actor::new("alice", vec!["admin".to_owned()]));
+        assert!(matches!(forged, Err(AuthError::InvalidToken(_))));
+        assert!(matches!(expired, Err(AuthError::InvalidToken(_))));
+        assert!(matches!(
+            jwt.authenticate(&HeaderMap::new()),
+            Err(AuthError::MissingCredentials)
+        ));
+    }
+
+    #[test]
+    fn jwt_audience_and_issuer_are_required_once_configured() {
+        let jwt = JwtAuthenticator::hs256(b"secret")
+            .with_audience("tickets")
+            .with_issuer("https://auth.example.com");
+        let exp = chrono::Utc::now().timestamp() + 60;
+        let token = |aud: Option<&str>, iss: Option<&str>| {
+            let mut claims = serde_json::json!({ "sub": "alice", "exp": exp });
+            if let Some(aud) = aud {
+                claims["aud"] = aud.into();
+            }
+            if let Some(iss) = iss {
+                claims["iss"] = iss.into();
+            }
+            jwt.authenticate(&bearer(b"secret", claims))
+        };
+        let (aud, iss) = (Some("tickets"), Some("https://auth.example.com"));
+
+        assert_eq!(token(aud, iss).unwrap().subject, "alice");
+        for rejected in [
+            token(Some("billing"), iss),
+            token(None, iss),
+            token(aud, Some("https://evil.example.com")),
+            token(aud, None),
+        ] {
+            assert!(matches!(rejected, Err(AuthError::InvalidToken(_))));
+        }
+    }
+
+    #[test]
+    fn api_keys_authenticate_as_their_name() {
+        let keys = ApiKeyAuthenticator::parse("ci=k1, backup=k2").unwrap();
+        let with_key = |key: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(API_KEY, HeaderValue::from_static(key));
+            headers
+        };
+
+        assert_eq!(
+            keys.authenticate(&with_key("k2")).unwrap().subject,
+            "backup"
+        );
+        assert!(matches!(
+            keys.authenticate(&with_key("k3")),
+            Err(AuthError::UnknownApiKey)
+        ));
+        assert!(ApiKeyAuthenticator::parse("ci").is_err());
+    }
+}
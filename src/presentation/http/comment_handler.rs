@@ -0,0 +1,122 @@
+use crate::application::usecase;
+use crate::domain::actor::Actor;
+use crate::presentation::http::comment_response::{CommentListResponse, CommentResponse};
+use crate::presentation::http::extract::{Json, Path};
+use crate::presentation::AppState;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug)]
+pub struct CommentRequest {
+    pub body: String,
+}
+
+#[tracing::instrument(
+    name = "GET /tickets/{id}/comments",
+    skip(service),
+    fields(id = %id)
+)]
+pub async fn list_comments(
+    State(service): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::comments::list_comments(service.uow_factory.as_ref(), id).await {
+        Ok(comments) => Json(CommentListResponse::from(comments.as_slice())).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "POST /tickets/{id}/comments",
+    skip(service, actor, request),
+    fields(id = %id)
+)]
+pub async fn add_comment(
+    State(service): State<AppState>,
+    actor: Actor,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CommentRequest>,
+) -> impl IntoResponse {
+    let comment = usecase::comments::add_comment(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        id,
+        request.body,
+    )
+    .await;
+    match comment {
+        Ok(comment) => {
+            let location = [(
+                header::LOCATION,
+                format!("/tickets/{id}/comments/{}", comment.id()),
+            )];
+            let body = Json(CommentResponse::from(&comment));
+            (StatusCode::CREATED, location, body).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "GET /tickets/{id}/comments/{comment_id}",
+    skip(service),
+    fields(id = %id, comment_id = %comment_id)
+)]
+pub async fn get_comment(
+    State(service): State<AppState>,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match usecase::comments::get_comment(service.uow_factory.as_ref(), id, comment_id).await {
+        Ok(comment) => Json(CommentResponse::from(&comment)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "PATCH /tickets/{id}/comments/{comment_id}",
+    skip(service, actor, request),
+    fields(id = %id, comment_id = %comment_id)
+)]
+pub async fn edit_comment(
+    State(service): State<AppState>,
+    actor: Actor,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<CommentRequest>,
+) -> impl IntoResponse {
+    let comment = usecase::comments::edit_comment(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        id,
+        comment_id,
+        request.body,
+    )
+    .await;
+    match comment {
+        Ok(comment) => Json(CommentResponse::from(&comment)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "DELETE /tickets/{id}/comments/{comment_id}",
+    skip(service, actor),
+    fields(id = %id, comment_id = %comment_id)
+)]
+pub async fn delete_comment(
+    State(service): State<AppState>,
+    actor: Actor,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let deleted =
+        usecase::comments::delete_comment(service.uow_factory.as_ref(), &actor, id, comment_id)
+            .await;
+    match deleted {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
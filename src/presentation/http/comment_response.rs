@@ -0,0 +1,40 @@
+use crate::domain::comments::comment::Comment;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct CommentResponse {
+    pub id: Uuid,
+    pub ticket_id: Uuid,
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&Comment> for CommentResponse {
+    fn from(comment: &Comment) -> Self {
+        Self {
+            id: comment.id().value(),
+            ticket_id: comment.ticket_id().value(),
+            author: comment.author().map(str::to_owned),
+            body: comment.body(),
+            created_at: comment.created_at(),
+            updated_at: comment.updated_at(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommentListResponse {
+    pub items: Vec<CommentResponse>,
+}
+
+impl From<&[Comment]> for CommentListResponse {
+    fn from(comments: &[Comment]) -> Self {
+        Self {
+            items: comments.iter().map(CommentResponse::from).collect(),
+        }
+    }
+}
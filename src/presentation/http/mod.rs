@@ -1,11 +1,60 @@
+mod comment_handler;
+mod comment_response;
+mod extract;
 mod ticket_handler;
+mod ticket_response;
+mod user_handler;
+mod user_response;
 
+use crate::presentation::{auth, trace_context};
 use crate::presentation::AppState;
-use axum::routing::{delete, post};
+use axum::middleware;
+use axum::routing::{get, post, put};
 use axum::Router;
 
-pub fn router() -> Router<AppState> {
+pub fn router(state: AppState) -> Router {
     Router::new()
-        .route("/tickets", post(ticket_handler::create_ticket))
-        .route("/tickets/{id}", delete(ticket_handler::close_ticket))
+        .route(
+            "/tickets",
+            get(ticket_handler::list_tickets).post(ticket_handler::create_ticket),
+        )
+        .route("/tickets:batch", post(ticket_handler::batch_tickets))
+        .route("/tickets:search", get(ticket_handler::search_tickets))
+        .route(
+            "/tickets/{id}",
+            get(ticket_handler::get_ticket)
+                .patch(ticket_handler::update_ticket)
+                .delete(ticket_handler::close_ticket),
+        )
+        .route(
+            "/tickets/{id}/assignee",
+            put(ticket_handler::assign_ticket).delete(ticket_handler::unassign_ticket),
+        )
+        .route("/tickets/{id}/start", post(ticket_handler::start_ticket))
+        .route("/tickets/{id}/resolve", post(ticket_handler::resolve_ticket))
+        .route("/tickets/{id}/reopen", post(ticket_handler::reopen_ticket))
+        .route("/tickets/{id}/history", get(ticket_handler::get_ticket_history))
+        .route(
+            "/tickets/{id}/labels/{label}",
+            put(ticket_handler::add_ticket_label).delete(ticket_handler::remove_ticket_label),
+        )
+        .route(
+            "/tickets/{id}/comments",
+            get(comment_handler::list_comments).post(comment_handler::add_comment),
+        )
+        .route(
+            "/tickets/{id}/comments/{comment_id}",
+            get(comment_handler::get_comment)
+                .patch(comment_handler::edit_comment)
+                .delete(comment_handler::delete_comment),
+        )
+        .route("/users", post(user_handler::register_user))
+        .route("/users/{id}", get(user_handler::get_user))
+        .route("/users/{id}/deactivate", post(user_handler::deactivate_user))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::authenticate,
+        ))
+        .layer(middleware::from_fn(trace_context::trace_requests))
+        .with_state(state)
 }
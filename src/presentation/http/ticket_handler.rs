@@ -1,49 +1,902 @@
 use crate::application::usecase;
+use crate::domain::actor::Actor;
+use crate::domain::clock::Clock;
+use crate::domain::error::DomainError;
+use crate::domain::idempotency::idempotency_error::IdempotencyError;
+use crate::domain::idempotency::idempotency_key::IdempotencyKey;
+use crate::domain::idempotency::idempotency_record::StoredResponse;
 use crate::domain::tickets::repository::UowFactory;
+use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_audit::AuditContext;
+use crate::domain::tickets::ticket_error::TicketError;
+use crate::domain::tickets::ticket_label::TicketLabel;
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::tickets::ticket_query::{TicketCursor, TicketQuery};
+use crate::domain::tickets::ticket_search::TicketSearch;
+use crate::domain::tickets::ticket_status::TicketStatusKind;
+use crate::domain::users::user_id::UserId;
+use crate::presentation::app_error::{FieldError, Problem};
+use crate::presentation::http::extract::{Json, Path, Query};
+use crate::presentation::http::ticket_response::{
+    TicketBatchResponse, TicketHistoryResponse, TicketListResponse, TicketResponse,
+    TicketSearchResponse,
+};
 use crate::presentation::AppState;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use axum::Json;
-use serde::Deserialize;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct CreateTicketRequest {
     pub title: String,
     pub description: String,
+    pub priority: Option<String>,
+    pub due_date: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpdateTicketRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    /// `null` clears the due date, leaving it out keeps it.
+    #[serde(default, deserialize_with = "present")]
+    pub due_date: Option<Option<NaiveDate>>,
+}
+
+/// Wraps any value that is present, including `null`, in `Some`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl TryFrom<UpdateTicketRequest> for usecase::tickets::TicketEdit {
+    type Error = DomainError;
+
+    fn try_from(request: UpdateTicketRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            title: request.title,
+            description: request.description,
+            priority: request
+                .priority
+                .map(|p| p.parse::<TicketPriority>())
+                .transpose()?,
+            due_date: request.due_date,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AssignTicketRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BatchTicketsRequest {
+    #[serde(default)]
+    pub mode: BatchModeRequest,
+    pub operations: Vec<BatchOperationRequest>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchModeRequest {
+    #[default]
+    AllOrNothing,
+    BestEffort,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperationRequest {
+    Close {
+        id: Uuid,
+    },
+    Assign {
+        id: Uuid,
+        user_id: Uuid,
+    },
+    /// `version` plays the part of `If-Match` on `PATCH /tickets/{id}`.
+    Update {
+        id: Uuid,
+        version: i64,
+        #[serde(flatten)]
+        changes: UpdateTicketRequest,
+    },
+}
+
+impl TryFrom<BatchOperationRequest> for usecase::tickets::BatchOperation {
+    type Error = DomainError;
+
+    fn try_from(request: BatchOperationRequest) -> Result<Self, Self::Error> {
+        Ok(match request {
+            BatchOperationRequest::Close { id } => Self::Close { id },
+            BatchOperationRequest::Assign { id, user_id } => Self::Assign { id, user_id },
+            BatchOperationRequest::Update {
+                id,
+                version,
+                changes,
+            } => Self::Update {
+                id,
+                expected_version: version,
+                edit: changes.try_into()?,
+            },
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ListTicketsParams {
+    pub status: Option<String>,
+    pub assignee: Option<Uuid>,
+    pub title: Option<String>,
+    pub priority: Option<String>,
+    pub overdue: Option<bool>,
+    /// Comma-separated labels, any of which a ticket must have.
+    pub labels_any: Option<String>,
+    /// Comma-separated labels, all of which a ticket must have.
+    pub labels_all: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl TryFrom<ListTicketsParams> for TicketQuery {
+    type Error = DomainError;
+
+    fn try_from(params: ListTicketsParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            status: params
+                .status
+                .map(|s| s.parse::<TicketStatusKind>())
+                .transpose()?,
+            assignee: params.assignee.map(UserId::from),
+            title: params.title.filter(|t| !t.trim().is_empty()),
+            priority: params
+                .priority
+                .map(|p| p.parse::<TicketPriority>())
+                .transpose()?,
+            overdue: params.overdue,
+            labels_any: parse_labels(params.labels_any)?,
+            labels_all: parse_labels(params.labels_all)?,
+            // Set by the use case from its clock
+            as_of: NaiveDate::default(),
+            cursor: params
+                .cursor
+                .as_deref()
+                .map(str::parse::<TicketCursor>)
+                .transpose()?,
+            limit: params.limit.unwrap_or(TicketQuery::DEFAULT_LIMIT),
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SearchTicketsParams {
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+}
+
+fn parse_labels(labels: Option<String>) -> Result<Vec<TicketLabel>, DomainError> {
+    let Some(labels) = labels else {
+        return Ok(Vec::new());
+    };
+    labels
+        .split(',')
+        .filter(|label| !label.trim().is_empty())
+        .map(|label| Ok(TicketLabel::try_from(label).map_err(TicketError::from)?))
+        .collect()
+}
+
+#[tracing::instrument(name = "GET /tickets", skip(service))]
+pub async fn list_tickets(
+    State(service): State<AppState>,
+    Query(params): Query<ListTicketsParams>,
+) -> impl IntoResponse {
+    let query = match TicketQuery::try_from(params) {
+        Ok(query) => query,
+        Err(e) => return e.into_response(),
+    };
+    let clock = service.clock.as_ref();
+    match usecase::tickets::list_tickets(service.uow_factory.as_ref(), clock, query).await {
+        Ok(page) => {
+            let today = clock.now().date_naive();
+            Json(TicketListResponse::new(&page, today)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(name = "GET /tickets:search", skip(service))]
+pub async fn search_tickets(
+    State(service): State<AppState>,
+    Query(params): Query<SearchTicketsParams>,
+) -> impl IntoResponse {
+    let limit = params.limit.unwrap_or(TicketSearch::DEFAULT_LIMIT);
+    let search = match TicketSearch::new(params.q.unwrap_or_default(), limit) {
+        Ok(search) => search,
+        Err(e) => return DomainError::from(e).into_response(),
+    };
+    match usecase::tickets::search_tickets(service.uow_factory.as_ref(), search).await {
+        Ok(hits) => {
+            let today = service.clock.now().date_naive();
+            Json(TicketSearchResponse::new(&hits, today)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
 }
 
 #[tracing::instrument(
     name = "POST /tickets",
-    skip(uow_factory),
+    skip(uow_factory, clock, actor, audit, headers),
     fields(title = %request.title, description = %request.description)
 )]
 pub async fn create_ticket(
     State(uow_factory): State<Arc<dyn UowFactory>>,
+    State(clock): State<Arc<dyn Clock>>,
+    actor: Actor,
+    audit: AuditContext,
+    headers: HeaderMap,
     Json(request): Json<CreateTicketRequest>,
 ) -> impl IntoResponse {
-    let id = usecase::tickets::create_ticket(
+    let priority = match request
+        .priority
+        .as_deref()
+        .map(str::parse::<TicketPriority>)
+        .transpose()
+    {
+        Ok(priority) => priority.unwrap_or_default(),
+        Err(e) => return DomainError::from(e).into_response(),
+    };
+    let idempotency_key = match headers
+        .get(IDEMPOTENCY_KEY)
+        .map(|value| IdempotencyKey::try_from(String::from_utf8_lossy(value.as_bytes()).as_ref()))
+        .transpose()
+    {
+        Ok(key) => key,
+        Err(e) => return DomainError::from(IdempotencyError::from(e)).into_response(),
+    };
+    let request_hash = request_hash(&request);
+    let new = usecase::tickets::NewTicket {
+        title: request.title,
+        description: request.description,
+        priority,
+        due_date: request.due_date,
+    };
+    let today = clock.now().date_naive();
+    let minimal = prefers_minimal(&headers);
+    let respond = move |ticket: &Ticket| created(ticket, today, minimal);
+
+    let Some(key) = idempotency_key else {
+        let ticket = usecase::tickets::create_ticket(
+            uow_factory.as_ref(),
+            clock.as_ref(),
+            &actor,
+            audit,
+            new,
+        )
+        .await;
+        return match ticket {
+            Ok(ticket) => stored_response(respond(&ticket)),
+            Err(e) => e.into_response(),
+        };
+    };
+    let request = usecase::tickets::IdempotentRequest {
+        key,
+        request_hash,
+        respond,
+    };
+    let response = usecase::tickets::create_ticket_idempotently(
         uow_factory.as_ref(),
-        request.title.to_string(),
-        request.description.to_string(),
+        clock.as_ref(),
+        &actor,
+        audit,
+        new,
+        request,
     )
     .await;
-    match id {
-        Ok(_) => StatusCode::CREATED.into_response(),
+    match response {
+        Ok(response) => stored_response(response),
         Err(e) => e.into_response(),
     }
 }
 
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Hex SHA-256 of the request body as parsed, so formatting does not matter.
+fn request_hash(request: &CreateTicketRequest) -> String {
+    let body = serde_json::to_vec(request).expect("requests serialize to JSON");
+    format!("{:x}", Sha256::digest(body))
+}
+
+/// The 201 response for a created ticket, in the form it is stored for
+/// idempotent retries.
+fn created(ticket: &Ticket, today: NaiveDate, minimal: bool) -> StoredResponse {
+    let mut headers = vec![(
+        header::LOCATION.to_string(),
+        format!("/tickets/{}", ticket.id()),
+    )];
+    let body = if minimal {
+        headers.push((PREFERENCE_APPLIED.to_string(), "return=minimal".to_owned()));
+        None
+    } else {
+        let body = TicketResponse::new(ticket, today);
+        Some(serde_json::to_string(&body).expect("responses serialize to JSON"))
+    };
+    StoredResponse {
+        status: StatusCode::CREATED.as_u16(),
+        headers,
+        body,
+    }
+}
+
+fn stored_response(stored: StoredResponse) -> Response {
+    let mut response = match stored.body {
+        Some(body) => ([(header::CONTENT_TYPE, "application/json")], body).into_response(),
+        None => ().into_response(),
+    };
+    *response.status_mut() =
+        StatusCode::from_u16(stored.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            response.headers_mut().append(name, value);
+        }
+    }
+    response
+}
+
+const PREFERENCE_APPLIED: HeaderName = HeaderName::from_static("preference-applied");
+
+/// Whether the client sent `Prefer: return=minimal` (RFC 7240).
+fn prefers_minimal(headers: &HeaderMap) -> bool {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|pref| pref.trim().eq_ignore_ascii_case("return=minimal"))
+}
+
+/// Responds 200 with a result per operation, unless an all-or-nothing batch
+/// failed, in which case it takes the status of the first failed operation.
+#[tracing::instrument(
+    name = "POST /tickets:batch",
+    skip(service, actor, audit, request),
+    fields(operations = request.operations.len())
+)]
+pub async fn batch_tickets(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Json(request): Json<BatchTicketsRequest>,
+) -> impl IntoResponse {
+    let max = usecase::tickets::BatchOperation::MAX_PER_BATCH;
+    if request.operations.len() > max {
+        return Problem::new(
+            StatusCode::BAD_REQUEST,
+            "validation-failed",
+            "Request validation failed",
+        )
+        .with_field_error(FieldError {
+            field: "operations",
+            code: "too_many",
+            message: format!("A batch can have at most {max} operations"),
+        })
+        .into_response();
+    }
+    let operations = match request
+        .operations
+        .into_iter()
+        .map(usecase::tickets::BatchOperation::try_from)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(operations) => operations,
+        Err(e) => return e.into_response(),
+    };
+    let mode = match request.mode {
+        BatchModeRequest::AllOrNothing => usecase::tickets::BatchMode::AllOrNothing,
+        BatchModeRequest::BestEffort => usecase::tickets::BatchMode::BestEffort,
+    };
+    let ids = operations.iter().map(|op| op.id()).collect::<Vec<_>>();
+
+    let outcomes = usecase::tickets::run_ticket_batch(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        mode,
+        operations,
+    )
+    .await;
+    let body = match outcomes {
+        Ok(outcomes) => TicketBatchResponse::new(&ids, outcomes, service.clock.now().date_naive()),
+        Err(e) => return e.into_response(),
+    };
+    let status = match mode {
+        usecase::tickets::BatchMode::AllOrNothing => body
+            .results
+            .iter()
+            .find(|item| item.error.is_some() && item.status != StatusCode::FAILED_DEPENDENCY)
+            .and_then(|item| StatusCode::from_u16(item.status).ok())
+            .unwrap_or(StatusCode::OK),
+        usecase::tickets::BatchMode::BestEffort => StatusCode::OK,
+    };
+    (status, Json(body)).into_response()
+}
+
 #[tracing::instrument(
     name = "DELETE /tickets/{id}",
-    skip(service),
+    skip(service, actor, audit),
     fields(id = %id)
 )]
 pub async fn close_ticket(
     State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    usecase::tickets::close_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "GET /tickets/{id}",
+    skip(service),
+    fields(id = %id)
+)]
+pub async fn get_ticket(
+    State(service): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::tickets::get_ticket(service.uow_factory.as_ref(), id).await {
+        Ok(ticket) => {
+            let etag = [(header::ETAG, etag(ticket.version()))];
+            let body = TicketResponse::new(&ticket, service.clock.now().date_naive());
+            (etag, Json(body)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "PATCH /tickets/{id}",
+    skip(service, actor, audit, headers),
+    fields(id = %id)
+)]
+pub async fn update_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<UpdateTicketRequest>,
+) -> impl IntoResponse {
+    let Some(if_match) = headers.get(header::IF_MATCH) else {
+        return Problem::new(
+            StatusCode::PRECONDITION_REQUIRED,
+            "if-match-required",
+            "If-Match header is required",
+        )
+        .with_detail("Send the ETag of the ticket you are editing in If-Match.")
+        .into_response();
+    };
+    let Some(expected_version) = parse_etag(if_match) else {
+        return Problem::new(
+            StatusCode::BAD_REQUEST,
+            "invalid-if-match",
+            "Invalid If-Match header",
+        )
+        .with_detail("If-Match must be an ETag returned by this API.")
+        .into_response();
+    };
+    let edit = match usecase::tickets::TicketEdit::try_from(request) {
+        Ok(edit) => edit,
+        Err(e) => return e.into_response(),
+    };
+
+    let ticket = usecase::tickets::update_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+        expected_version,
+        edit,
+    )
+    .await;
+    match ticket {
+        Ok(ticket) => {
+            let etag = [(header::ETAG, etag(ticket.version()))];
+            let body = TicketResponse::new(&ticket, service.clock.now().date_naive());
+            (etag, Json(body)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "GET /tickets/{id}/history",
+    skip(service),
+    fields(id = %id)
+)]
+pub async fn get_ticket_history(
+    State(service): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::tickets::get_ticket_history(service.uow_factory.as_ref(), id).await {
+        Ok(entries) => Json(TicketHistoryResponse::from(entries.as_slice())).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Strong ETag of a ticket, derived from its version.
+fn etag(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+fn parse_etag(value: &HeaderValue) -> Option<i64> {
+    value
+        .to_str()
+        .ok()?
+        .trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')?
+        .parse()
+        .ok()
+}
+
+#[tracing::instrument(
+    name = "PUT /tickets/{id}/assignee",
+    skip(service, actor, audit),
+    fields(id = %id, user_id = %request.user_id)
+)]
+pub async fn assign_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+    Json(request): Json<AssignTicketRequest>,
+) -> impl IntoResponse {
+    usecase::tickets::assign_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+        request.user_id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "DELETE /tickets/{id}/assignee",
+    skip(service, actor, audit),
+    fields(id = %id)
+)]
+pub async fn unassign_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    usecase::tickets::unassign_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "POST /tickets/{id}/start",
+    skip(service, actor, audit),
+    fields(id = %id)
+)]
+pub async fn start_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    usecase::tickets::start_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "POST /tickets/{id}/resolve",
+    skip(service, actor, audit),
+    fields(id = %id)
+)]
+pub async fn resolve_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    usecase::tickets::resolve_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "POST /tickets/{id}/reopen",
+    skip(service, actor, audit),
+    fields(id = %id)
+)]
+pub async fn reopen_ticket(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
-    usecase::tickets::close_ticket(service.uow_factory.as_ref(), id).await
+    usecase::tickets::reopen_ticket(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "PUT /tickets/{id}/labels/{label}",
+    skip(service, actor, audit),
+    fields(id = %id, label = %label)
+)]
+pub async fn add_ticket_label(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path((id, label)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    usecase::tickets::add_ticket_label(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+        label,
+    )
+    .await
+}
+
+#[tracing::instrument(
+    name = "DELETE /tickets/{id}/labels/{label}",
+    skip(service, actor, audit),
+    fields(id = %id, label = %label)
+)]
+pub async fn remove_ticket_label(
+    State(service): State<AppState>,
+    actor: Actor,
+    audit: AuditContext,
+    Path((id, label)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    usecase::tickets::remove_ticket_label(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        audit,
+        id,
+        label,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::clock::FixedClock;
+    use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+    use crate::presentation::auth::NoAuthentication;
+    use crate::presentation::{http, AppState};
+    use axum::body::Body;
+    use axum::http::{header, Method, Request, StatusCode};
+    use axum::response::Response;
+    use axum::Router;
+    use chrono::{TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let clock = Arc::new(FixedClock(
+            Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap(),
+        ));
+        http::router(AppState {
+            uow_factory: Arc::new(InMemoryUowFactory::new(clock.clone())),
+            clock,
+            authenticator: Arc::new(NoAuthentication),
+        })
+    }
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        if_match: Option<&str>,
+        body: Value,
+    ) -> Response {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(etag) = if_match {
+            request = request.header(header::IF_MATCH, etag);
+        }
+        let request = request.body(Body::from(body.to_string())).unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    /// Creates a ticket and returns its URI and ETag.
+    async fn create(app: &Router) -> (String, String) {
+        let body = json!({ "title": "Printer is jammed", "description": "Paper stuck in tray 2" });
+        let response = send(app, Method::POST, "/tickets", None, body).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let id = json_body(response).await["id"].as_str().unwrap().to_owned();
+        let uri = format!("/tickets/{id}");
+        let response = send(app, Method::GET, &uri, None, Value::Null).await;
+        let etag = response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        (uri, etag)
+    }
+
+    #[tokio::test]
+    async fn patch_with_the_current_etag_updates_and_returns_the_next_etag() {
+        let app = app();
+        let (uri, etag) = create(&app).await;
+
+        let response = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(&etag),
+            json!({ "title": "Printer is on fire" }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag.as_str());
+        assert_eq!(json_body(response).await["title"], "Printer is on fire");
+    }
+
+    #[tokio::test]
+    async fn patch_with_a_stale_etag_fails_the_precondition() {
+        let app = app();
+        let (uri, stale) = create(&app).await;
+        let response = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(&stale),
+            json!({ "priority": "high" }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(&stale),
+            json!({ "title": "Printer is on fire" }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        let current = json_body(send(&app, Method::GET, &uri, None, Value::Null).await).await;
+        assert_eq!(current["title"], "Printer is jammed");
+    }
+
+    #[tokio::test]
+    async fn patch_without_if_match_requires_the_precondition() {
+        let app = app();
+        let (uri, _) = create(&app).await;
+
+        let response = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            None,
+            json!({ "title": "Printer is on fire" }),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+        let current = json_body(send(&app, Method::GET, &uri, None, Value::Null).await).await;
+        assert_eq!(current["title"], "Printer is jammed");
+    }
+
+    async fn assert_problem(response: Response, status: StatusCode, code: &str) {
+        assert_eq!(response.status(), status);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        assert_eq!(json_body(response).await["code"], code);
+    }
+
+    #[tokio::test]
+    async fn malformed_ids_in_the_path_are_problems() {
+        let response = send(&app(), Method::GET, "/tickets/42", None, Value::Null).await;
+
+        assert_problem(response, StatusCode::BAD_REQUEST, "invalid-path-parameter").await;
+    }
+
+    #[tokio::test]
+    async fn malformed_json_bodies_are_problems() {
+        let request = Request::post("/tickets")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{\"title\":"))
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+
+        assert_problem(response, StatusCode::BAD_REQUEST, "invalid-body").await;
+    }
+
+    #[tokio::test]
+    async fn malformed_query_strings_are_problems() {
+        let response = send(&app(), Method::GET, "/tickets?limit=ten", None, Value::Null).await;
+
+        assert_problem(response, StatusCode::BAD_REQUEST, "invalid-query").await;
+    }
+
+    #[tokio::test]
+    async fn audit_entries_keep_the_server_trace_id_not_the_client_request_id() {
+        let app = app();
+        let request = Request::post("/tickets")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-request-id", "forged-trace-id")
+            .body(Body::from(
+                json!({ "title": "Printer is jammed", "description": "Paper stuck in tray 2" })
+                    .to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let trace_id = response.headers()["x-request-id"]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let id = json_body(response).await["id"].as_str().unwrap().to_owned();
+
+        let uri = format!("/tickets/{id}/history");
+        let history = json_body(send(&app, Method::GET, &uri, None, Value::Null).await).await;
+
+        assert_ne!(trace_id, "forged-trace-id");
+        assert_eq!(history["items"][0]["trace_id"], trace_id.as_str());
+    }
 }
@@ -0,0 +1,256 @@
+use crate::application::usecase::tickets::BatchOutcome;
+use crate::domain::tickets::ticket::Ticket;
+use crate::domain::tickets::ticket_audit::{TicketAuditEntry, TicketSnapshot};
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::domain::tickets::ticket_query::TicketPage;
+use crate::domain::tickets::ticket_search::TicketSearchHit;
+use crate::domain::tickets::ticket_status::TicketStatus;
+use crate::presentation::app_error::Problem;
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct TicketResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub status: TicketStatusResponse,
+    pub assignee: Option<Uuid>,
+    pub reporter: Option<String>,
+    pub priority: TicketPriority,
+    pub due_date: Option<NaiveDate>,
+    pub overdue: bool,
+    pub labels: Vec<String>,
+    pub version: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum TicketStatusResponse {
+    Open,
+    Assigned { user_id: Uuid },
+    InProgress { user_id: Uuid },
+    Resolved,
+    Closed,
+}
+
+impl From<TicketStatus> for TicketStatusResponse {
+    fn from(status: TicketStatus) -> Self {
+        match status {
+            TicketStatus::Open => Self::Open,
+            TicketStatus::Assigned { user_id } => Self::Assigned {
+                user_id: user_id.value(),
+            },
+            TicketStatus::InProgress { user_id } => Self::InProgress {
+                user_id: user_id.value(),
+            },
+            TicketStatus::Resolved => Self::Resolved,
+            TicketStatus::Closed => Self::Closed,
+        }
+    }
+}
+
+impl TicketResponse {
+    /// `today` is the day `overdue` is computed for.
+    pub fn new(ticket: &Ticket, today: NaiveDate) -> Self {
+        let timestamps = ticket.timestamps();
+        Self {
+            id: ticket.id().value(),
+            title: ticket.title(),
+            description: ticket.description(),
+            status: ticket.status().into(),
+            assignee: ticket.assignee().map(|a| a.value()),
+            reporter: ticket.reporter().map(str::to_owned),
+            priority: ticket.priority(),
+            due_date: ticket.due_date(),
+            overdue: ticket.is_overdue(today),
+            labels: ticket.labels(),
+            version: ticket.version(),
+            created_at: timestamps.created_at,
+            updated_at: timestamps.updated_at,
+            closed_at: timestamps.closed_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketListResponse {
+    pub items: Vec<TicketResponse>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
+}
+
+impl TicketListResponse {
+    pub fn new(page: &TicketPage, today: NaiveDate) -> Self {
+        Self {
+            items: page
+                .items
+                .iter()
+                .map(|ticket| TicketResponse::new(ticket, today))
+                .collect(),
+            next_cursor: page.next_cursor.map(|c| c.to_string()),
+            total: page.total,
+        }
+    }
+}
+
+/// `title` and `snippet` are HTML: the ticket text in them is escaped, so
+/// the `<mark>` around matches is the only markup.
+#[derive(Serialize, Debug)]
+pub struct TicketSearchResponse {
+    pub items: Vec<TicketSearchHitResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketSearchHitResponse {
+    pub ticket: TicketResponse,
+    pub rank: f32,
+    pub title: String,
+    pub snippet: String,
+}
+
+impl TicketSearchResponse {
+    pub fn new(hits: &[TicketSearchHit], today: NaiveDate) -> Self {
+        Self {
+            items: hits
+                .iter()
+                .map(|hit| TicketSearchHitResponse {
+                    ticket: TicketResponse::new(&hit.ticket, today),
+                    rank: hit.rank,
+                    title: hit.title.clone(),
+                    snippet: hit.snippet.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketHistoryResponse {
+    pub items: Vec<TicketAuditEntryResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketAuditEntryResponse {
+    pub previous: Option<TicketSnapshotResponse>,
+    pub current: TicketSnapshotResponse,
+    pub actor: Option<String>,
+    pub trace_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketSnapshotResponse {
+    pub title: String,
+    pub description: String,
+    pub status: String,
+    pub assignee: Option<Uuid>,
+    pub priority: TicketPriority,
+    pub due_date: Option<NaiveDate>,
+    pub labels: Vec<String>,
+    pub version: i64,
+}
+
+impl From<&TicketSnapshot> for TicketSnapshotResponse {
+    fn from(snapshot: &TicketSnapshot) -> Self {
+        Self {
+            title: snapshot.title.clone(),
+            description: snapshot.description.clone(),
+            status: snapshot.status.clone(),
+            assignee: snapshot.assignee,
+            priority: snapshot.priority,
+            due_date: snapshot.due_date,
+            labels: snapshot.labels.clone(),
+            version: snapshot.version,
+        }
+    }
+}
+
+impl From<&TicketAuditEntry> for TicketAuditEntryResponse {
+    fn from(entry: &TicketAuditEntry) -> Self {
+        Self {
+            previous: entry.previous.as_ref().map(TicketSnapshotResponse::from),
+            current: TicketSnapshotResponse::from(&entry.current),
+            actor: entry.actor.clone(),
+            trace_id: entry.trace_id.clone(),
+            recorded_at: entry.recorded_at,
+        }
+    }
+}
+
+impl From<&[TicketAuditEntry]> for TicketHistoryResponse {
+    fn from(entries: &[TicketAuditEntry]) -> Self {
+        Self {
+            items: entries.iter().map(TicketAuditEntryResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct TicketBatchResponse {
+    pub applied: usize,
+    pub failed: usize,
+    pub results: Vec<TicketBatchItemResponse>,
+}
+
+/// Outcome of one operation, with the status it would have had on its own.
+#[derive(Serialize, Debug)]
+pub struct TicketBatchItemResponse {
+    pub index: usize,
+    pub id: Uuid,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<TicketResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Problem>,
+}
+
+impl TicketBatchResponse {
+    /// `ids` are the tickets the operations were on, in the same order as
+    /// `outcomes`.
+    pub fn new(ids: &[Uuid], outcomes: Vec<BatchOutcome>, today: NaiveDate) -> Self {
+        let results = ids
+            .iter()
+            .zip(outcomes)
+            .enumerate()
+            .map(|(index, (&id, outcome))| {
+                let (ticket, error) = match outcome {
+                    BatchOutcome::Applied(ticket) => {
+                        (Some(TicketResponse::new(&ticket, today)), None)
+                    }
+                    BatchOutcome::Failed(e) => (None, Some(Problem::from(e))),
+                    BatchOutcome::RolledBack => (
+                        None,
+                        Some(
+                            Problem::new(
+                                StatusCode::FAILED_DEPENDENCY,
+                                "rolled-back",
+                                "Rolled back",
+                            )
+                            .with_detail("Another operation in the batch failed."),
+                        ),
+                    ),
+                };
+                let status = error.as_ref().map_or(StatusCode::OK, Problem::status);
+                TicketBatchItemResponse {
+                    index,
+                    id,
+                    status: status.as_u16(),
+                    ticket,
+                    error,
+                }
+            })
+            .collect::<Vec<_>>();
+        let applied = results.iter().filter(|item| item.ticket.is_some()).count();
+        Self {
+            applied,
+            failed: results.len() - applied,
+            results,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::application::usecase;
+use crate::domain::actor::Actor;
+use crate::presentation::http::extract::{Json, Path};
+use crate::presentation::http::user_response::UserResponse;
+use crate::presentation::AppState;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize, Debug)]
+pub struct RegisterUserRequest {
+    pub name: String,
+}
+
+#[tracing::instrument(name = "POST /users", skip(service, actor, request))]
+pub async fn register_user(
+    State(service): State<AppState>,
+    actor: Actor,
+    Json(request): Json<RegisterUserRequest>,
+) -> impl IntoResponse {
+    let user = usecase::users::register_user(
+        service.uow_factory.as_ref(),
+        service.clock.as_ref(),
+        &actor,
+        request.name,
+    )
+    .await;
+    match user {
+        Ok(user) => {
+            let location = [(header::LOCATION, format!("/users/{}", user.id()))];
+            (
+                StatusCode::CREATED,
+                location,
+                Json(UserResponse::from(&user)),
+            )
+                .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(name = "GET /users/{id}", skip(service), fields(id = %id))]
+pub async fn get_user(State(service): State<AppState>, Path(id): Path<Uuid>) -> impl IntoResponse {
+    match usecase::users::get_user(service.uow_factory.as_ref(), id).await {
+        Ok(user) => Json(UserResponse::from(&user)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[tracing::instrument(
+    name = "POST /users/{id}/deactivate",
+    skip(service, actor),
+    fields(id = %id)
+)]
+pub async fn deactivate_user(
+    State(service): State<AppState>,
+    actor: Actor,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::users::deactivate_user(service.uow_factory.as_ref(), &actor, id).await {
+        Ok(user) => Json(UserResponse::from(&user)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
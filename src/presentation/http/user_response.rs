@@ -0,0 +1,23 @@
+use crate::domain::users::user::User;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct UserResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&User> for UserResponse {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id().value(),
+            name: user.name(),
+            active: user.is_active(),
+            created_at: user.created_at(),
+        }
+    }
+}
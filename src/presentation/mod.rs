@@ -1,13 +1,19 @@
+use crate::domain::clock::Clock;
 use crate::domain::tickets::repository::UowFactory;
+use crate::presentation::auth::Authenticator;
 use axum::extract::FromRef;
 use std::sync::Arc;
 
+pub mod auth;
 pub mod http;
 mod app_error;
+mod trace_context;
 
 #[derive(Clone)]
 pub struct AppState {
     pub uow_factory: Arc<dyn UowFactory>,
+    pub clock: Arc<dyn Clock>,
+    pub authenticator: Arc<dyn Authenticator>,
 }
 
 impl FromRef<AppState> for Arc<dyn UowFactory> {
@@ -15,3 +21,9 @@ impl FromRef<AppState> for Arc<dyn UowFactory> {
         input.uow_factory.clone()
     }
 }
+
+impl FromRef<AppState> for Arc<dyn Clock> {
+    fn from_ref(input: &AppState) -> Self {
+        input.clock.clone()
+    }
+}
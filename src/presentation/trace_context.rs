@@ -0,0 +1,85 @@
+use crate::domain::tickets::ticket_audit::AuditContext;
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use opentelemetry::trace::TraceContextExt;
+use std::convert::Infallible;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
+
+pub const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Gives every request a trace id that is echoed in `X-Request-Id` and in
+/// error responses, so a client report can be matched with our traces.
+///
+/// The trace id is the OpenTelemetry trace id of the request span when
+/// tracing is exported, and a random id when it is not. It is never taken
+/// from the client, as it is also written to the audit log; a well-formed
+/// `X-Request-Id` sent by the client is only recorded on the span as
+/// `client_request_id`.
+pub async fn trace_requests(request: Request, next: Next) -> Response {
+    let span = tracing::info_span!(
+        "http.request",
+        method = %request.method(),
+        uri = %request.uri(),
+        trace_id = tracing::field::Empty,
+        client_request_id = tracing::field::Empty,
+        actor = tracing::field::Empty,
+    );
+    if let Some(client_request_id) = request
+        .headers()
+        .get(REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+    {
+        span.record("client_request_id", client_request_id);
+    }
+    let span_context = span.context().span().span_context().clone();
+    let trace_id = if span_context.is_valid() {
+        span_context.trace_id().to_string()
+    } else {
+        Uuid::new_v4().simple().to_string()
+    };
+    span.record("trace_id", &trace_id);
+
+    let mut response = TRACE_ID
+        .scope(trace_id.clone(), next.run(request).instrument(span))
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&trace_id) {
+        response.headers_mut().insert(REQUEST_ID, value);
+    }
+    response
+}
+
+/// The trace id of the request being handled, if any.
+pub fn current_trace_id() -> Option<String> {
+    TRACE_ID.try_with(Clone::clone).ok()
+}
+
+/// Lets handlers take the audit context of the request as an argument.
+impl<S: Send + Sync> FromRequestParts<S> for AuditContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(AuditContext {
+            // Filled in by the use case from the authenticated actor
+            actor: None,
+            trace_id: current_trace_id(),
+        })
+    }
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 128
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
@@ -0,0 +1,39 @@
+//! Setup shared by the tests: a fixed day, the usual caller and one ticket.
+
+use crate::application::usecase::tickets::{create_ticket, NewTicket};
+use crate::domain::actor::Actor;
+use crate::domain::clock::FixedClock;
+use crate::domain::tickets::ticket_audit::AuditContext;
+use crate::domain::tickets::ticket_priority::TicketPriority;
+use crate::infrastructure::repository::in_memory_ticket_repository::InMemoryUowFactory;
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+/// The caller of tests that are not about permissions.
+pub fn actor() -> Actor {
+    Actor::new("tester", vec![Actor::ADMIN.to_owned()])
+}
+
+/// A clock stopped at `hour` o'clock on the day the tests run on.
+pub fn at(hour: u32) -> FixedClock {
+    FixedClock(Utc.with_ymd_and_hms(2026, 1, 1, hour, 0, 0).unwrap())
+}
+
+/// Creates a ticket as `actor()` and returns its id.
+pub async fn create(fac: &InMemoryUowFactory, clock: &FixedClock) -> Uuid {
+    let ticket = create_ticket(
+        fac,
+        clock,
+        &actor(),
+        AuditContext::default(),
+        NewTicket {
+            title: "Printer is jammed".to_owned(),
+            description: "Paper stuck in tray 2".to_owned(),
+            priority: TicketPriority::default(),
+            due_date: None,
+        },
+    )
+    .await
+    .unwrap();
+    ticket.id().value()
+}